@@ -1,5 +1,3 @@
-use core::fmt::Debug;
-
 use embedded_graphics::mono_font::iso_8859_10::FONT_10X20;
 use embedded_graphics::pixelcolor::Rgb888;
 use embedded_graphics::{
@@ -8,11 +6,25 @@ use embedded_graphics::{
     text::{Alignment, LineHeight, Text, TextStyleBuilder},
 };
 use epd_waveshare::color::Color;
+use epd_waveshare::epd7in5_v2::{HEIGHT, WIDTH};
+use epd_waveshare::graphics::VarDisplay;
 use heapless::String;
 use tinyqoi::Qoi;
 
 use crate::task::state::ProgramState;
 
+/// Size of the epd7in5_v2 framebuffer, 1 bit per pixel
+pub const FRAME_BYTES: usize = epd_waveshare::buffer_len(WIDTH as usize, HEIGHT as usize);
+
+/// Header of a packed framebuffer from the scraper: magic, width and height as little-endian u16, number of planes
+const EPD_MAGIC: &[u8; 4] = b"epdf";
+const EPD_HEADER_BYTES: usize = 9;
+
+#[derive(Debug)]
+pub enum DrawError {
+    InvalidImage,
+}
+
 #[derive(PartialEq)]
 pub enum DisplayCommand {
     ShowStatusText(String<30>, i32),
@@ -21,34 +33,37 @@ pub enum DisplayCommand {
 }
 
 impl DisplayCommand {
-    pub fn draw<D, E>(&self, target: &mut D, state: &ProgramState) -> Result<(), E>
-    where
-        E: Debug,
-        D: DrawTarget<Color = Color, Error = E>,
-    {
+    /// Draw the command into `frame`, the raw framebuffer that is sent to the panel
+    pub fn draw(&self, frame: &mut [u8], state: &ProgramState) -> Result<(), DrawError> {
         match self {
             DisplayCommand::ShowStatusText(text, line_number) => {
+                let mut canvas = VarDisplay::<Color>::new(WIDTH, HEIGHT, frame, false).unwrap();
                 let text_style = TextStyleBuilder::new()
                     .alignment(Alignment::Left)
                     .line_height(LineHeight::Percent(150))
                     .build();
-                Text::with_text_style(
+                let _ = Text::with_text_style(
                     text.as_str(),
                     Point::new(20, 20 + (16 * line_number)),
                     MonoTextStyle::new(&FONT_10X20, Color::White),
                     text_style,
                 )
-                .draw(target)?;
+                .draw(&mut canvas);
                 Ok(())
             }
             DisplayCommand::DrawImage(screen_idx) => {
+                let image = state.get_buffer_for_screen(*screen_idx).ok_or(DrawError::InvalidImage)?;
+                if image.starts_with(EPD_MAGIC) {
+                    return unpack_epd(image, frame);
+                }
+                let mut canvas = VarDisplay::<Color>::new(WIDTH, HEIGHT, frame, false).unwrap();
                 let zero = Point::zero();
-                let image = Qoi::new(state.get_buffer_for_screen(*screen_idx).unwrap()).expect("Failed to parse image");
+                let image = Qoi::new(image).map_err(|_| DrawError::InvalidImage)?;
                 let pixels = image
                     .pixels()
                     .enumerate()
                     .map(|(i, p)| Pixel(zero + Point::new(i as i32 % 800, i as i32 / 800), rgb888_to_bw(p)));
-                let _ = target.draw_iter(pixels);
+                let _ = canvas.draw_iter(pixels);
                 Ok(())
             }
             DisplayCommand::DisplayPowerOff => Ok(()),
@@ -56,6 +71,49 @@ impl DisplayCommand {
     }
 }
 
+/// Decompress a packed framebuffer straight into `frame`.
+/// A second (chromatic) plane is merged into the first, as this panel can only show black.
+fn unpack_epd(image: &[u8], frame: &mut [u8]) -> Result<(), DrawError> {
+    if image.len() < EPD_HEADER_BYTES
+        || u16::from_le_bytes([image[4], image[5]]) as u32 != WIDTH
+        || u16::from_le_bytes([image[6], image[7]]) as u32 != HEIGHT
+    {
+        return Err(DrawError::InvalidImage);
+    }
+    let planes = image[8] as usize;
+    let data = &image[EPD_HEADER_BYTES..];
+
+    // PackBits: control byte n < 128 is followed by n + 1 literal bytes, n > 128 by one byte repeated 257 - n times
+    let (mut i, mut out) = (0, 0);
+    while i < data.len() {
+        let control = data[i] as usize;
+        i += 1;
+        let (count, literal) = match control {
+            0..128 => (control + 1, true),
+            128 => continue,
+            _ => (257 - control, false),
+        };
+        if out + count > FRAME_BYTES * planes || i + if literal { count } else { 1 } > data.len() {
+            return Err(DrawError::InvalidImage);
+        }
+        for j in 0..count {
+            let byte = if literal { data[i + j] } else { data[i] };
+            let idx = out + j;
+            if idx < FRAME_BYTES {
+                frame[idx] = byte;
+            } else {
+                frame[idx - FRAME_BYTES] |= byte;
+            }
+        }
+        i += if literal { count } else { 1 };
+        out += count;
+    }
+    if out != FRAME_BYTES * planes {
+        return Err(DrawError::InvalidImage);
+    }
+    Ok(())
+}
+
 fn rgb888_to_bw(color: Rgb888) -> Color {
     // Use luminance formula: 0.299*R + 0.587*G + 0.114*B
     let luminance = 0.299 * (color.r() as f32) + 0.587 * (color.g() as f32) + 0.114 * (color.b() as f32);
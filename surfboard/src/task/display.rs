@@ -2,7 +2,7 @@ use core::cell::RefCell;
 
 use crate::{
     system::{
-        drawing::DisplayCommand,
+        drawing::{DisplayCommand, FRAME_BYTES},
         event::{send_event, Events},
         resources::ScreenResources,
     },
@@ -19,8 +19,6 @@ use embassy_sync::blocking_mutex::raw::NoopRawMutex;
 use embassy_sync::blocking_mutex::Mutex;
 use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel};
 use embassy_time::Delay;
-use embedded_graphics::prelude::*;
-use epd_waveshare::epd7in5_v2::Display7in5;
 use epd_waveshare::epd7in5_v2::Epd7in5;
 use epd_waveshare::prelude::WaveshareDisplay;
 use static_cell::StaticCell;
@@ -110,9 +108,9 @@ pub async fn start(r: ScreenResources) {
     let mut display = init_display(r);
     display.sleep().expect("Failed to put screen to sleep");
 
-    // clear display
-    let mut canvas = Display7in5::default();
-    canvas.clear(epd_waveshare::color::Color::Black).unwrap();
+    // raw framebuffer, status text and QOI images are drawn through a `VarDisplay`, packed images are copied in
+    static FRAME: StaticCell<[u8; FRAME_BYTES]> = StaticCell::new();
+    let frame = FRAME.init([epd_waveshare::color::Color::Black.get_byte_value(); FRAME_BYTES]);
 
     loop {
         // Wait for the next display update request and clear the display
@@ -128,16 +126,26 @@ pub async fn start(r: ScreenResources) {
         {
             let state_guard = STATE_MANAGER_MUTEX.lock().await;
             display_action
-                .draw(&mut canvas, &*state_guard)
+                .draw(frame, &*state_guard)
                 .expect("Failed to draw splash screen");
         }
 
-        display.draw(canvas.buffer()).expect("Failed to draw on screen");
+        display.draw(frame).expect("Failed to draw on screen");
         display.sleep().expect("Failed to put screen to sleep");
 
+        let mut state_guard = STATE_MANAGER_MUTEX.lock().await;
+
+        // remember which image is on the panel so an unchanged screen isn't downloaded and redrawn
+        let drawn_hash = match &display_action {
+            DisplayCommand::DrawImage(screen_idx) => state_guard
+                .screen_configuration(*screen_idx)
+                .and_then(|screen| screen.hash.clone()),
+            _ => None,
+        };
+        state_guard.drawn_hash = drawn_hash;
+
         // after drawing an image, let's clear out the buffer so it's ready to use again
         if let DisplayCommand::DrawImage(screen_idx) = display_action {
-            let mut state_guard = STATE_MANAGER_MUTEX.lock().await;
             state_guard.forget_screen_idx(screen_idx);
             send_event(Events::ScreenDrawn(screen_idx)).await;
         }
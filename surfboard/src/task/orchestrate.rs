@@ -42,14 +42,9 @@ async fn process_event<'a>(event: Events) {
     match event {
         // Load screensaveer on timeout
         Events::OrchestratorTimeout => {
-            // fetch screensaver
-            let mut state_guard = STATE_MANAGER_MUTEX.lock().await;
-            state_guard.screen_index = SCREEN_SAVER_SCREEN_IDX;
-            if let Some(config) = &state_guard.config {
-                if let Some(screen_saver) = &config.screen_saver {
-                    wifi_command(WifiCommand::LoadScreen(SCREEN_SAVER_SCREEN_IDX, screen_saver.clone())).await;
-                }
-            }
+            // reload the configuration first, the scraper publishes new images under new URLs and deletes old ones
+            STATE_MANAGER_MUTEX.lock().await.screen_index = SCREEN_SAVER_SCREEN_IDX;
+            wifi_command(WifiCommand::LoadConfiguration).await;
         }
         Events::ScreenDrawn(screen_idx) => {
             // if the screen-saver was drawn then we are part of the sleep cycle, sleep for longer
@@ -69,11 +64,16 @@ async fn process_event<'a>(event: Events) {
 
             // check if the screen being requested has already been loaded
             let screen_index = state_guard.screen_index;
-            if let Some(_) = state_guard.get_buffer_for_screen(screen_index) {
+            if state_guard
+                .screen_configuration(screen_index)
+                .is_some_and(|screen| state_guard.is_drawn(screen))
+            {
+                // the panel already shows this image
+                send_event(Events::ScreenDrawn(screen_index)).await;
+            } else if let Some(_) = state_guard.get_buffer_for_screen(screen_index) {
                 send_event(Events::ScreenLoaded(screen_index)).await;
-            } else if let Some(config) = &state_guard.config {
-                let screen_config = config.screens.iter().nth(state_guard.screen_index).unwrap().clone();
-                wifi_command(WifiCommand::LoadScreen(state_guard.screen_index, screen_config)).await;
+            } else if let Some(screen_config) = state_guard.screen_configuration(screen_index) {
+                wifi_command(WifiCommand::LoadScreen(screen_index, screen_config.clone())).await;
             }
 
             // NOTE(yurig): disable pre-fetching for simplicity!
@@ -12,6 +12,10 @@ pub struct Configuration {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ScreenConfiguration {
     pub url: String<128>,
+
+    // content hash of the published image, the same hash means the same pixels
+    #[serde(default)]
+    pub hash: Option<String<32>>,
 }
 
 const NUM_SCREEN_CONFIGURATIONS: usize = 5;
@@ -32,6 +36,9 @@ pub struct ProgramState {
 
     // vector of buffers that can be forgotten
     pub can_forget: Vec<usize, NUM_SCREEN_BUFFERS>,
+
+    // hash of the image on the panel, None after status text or an image without a hash
+    pub drawn_hash: Option<String<32>>,
 }
 
 impl ProgramState {
@@ -43,6 +50,28 @@ impl ProgramState {
         }
     }
 
+    /// Replace the configuration, screens buffered for the old one may point at deleted images
+    pub fn set_config(&mut self, config: Configuration) {
+        self.config = Some(config);
+        self.buffer_map.clear();
+        self.can_forget.clear();
+    }
+
+    /// Configuration of a screen index, including the screen saver's
+    pub fn screen_configuration(&self, screen_idx: usize) -> Option<&ScreenConfiguration> {
+        let config = self.config.as_ref()?;
+        if screen_idx == SCREEN_SAVER_SCREEN_IDX {
+            config.screen_saver.as_ref()
+        } else {
+            config.screens.get(screen_idx)
+        }
+    }
+
+    /// Whether the panel already shows this screen's published image, so it needs no download or refresh
+    pub fn is_drawn(&self, screen: &ScreenConfiguration) -> bool {
+        screen.hash.is_some() && screen.hash == self.drawn_hash
+    }
+
     pub fn move_to_next_screen(&mut self) {
         if let Some(idx) = self.next_screen_idx() {
             self.screen_index = idx;
@@ -100,4 +129,5 @@ pub static STATE_MANAGER_MUTEX: Mutex<CriticalSectionRawMutex, ProgramState> = M
     buffers: [Vec::new(), Vec::new()],
     buffer_map: LinearMap::new(),
     can_forget: Vec::new(),
+    drawn_hash: None,
 });
@@ -46,7 +46,7 @@ async fn net_task(runner: &'static mut Runner<'static, cyw43::NetDriver<'static>
 
 
 pub static CONFIG_LOCATION: &'static str = concat!(
-      "https://yurig-public.s3.us-east-1.amazonaws.com/",
+      env!("CONFIG_BASE_URL"),
       env!("DEVICE_ID"),
       ".json"
   );
@@ -187,9 +187,12 @@ pub async fn start(r: WifiResources, spawner: Spawner) {
                     if let Some(config) = config_result {
                         {
                             let mut state_guard = STATE_MANAGER_MUTEX.lock().await;
-                            state_guard.config = Some(config);
+                            state_guard.set_config(config);
                         }
                         send_event(Events::ConfigurationLoaded).await;
+                    } else if STATE_MANAGER_MUTEX.lock().await.config.is_some() {
+                        // keep showing the screens of the last configuration
+                        send_event(Events::ConfigurationLoaded).await;
                     } else {
                         send_error("Failed to load configuration").await;
                     }
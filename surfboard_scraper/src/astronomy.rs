@@ -0,0 +1,274 @@
+// Sun and moon positions for a location, following the low-precision formulas from
+// "Astronomical Algorithms" (Meeus) as popularized by suncalc. Accurate to a minute or two for sun times
+// and a few minutes for the moon, which is plenty for a surf report.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+const RAD: f64 = PI / 180.;
+const DAY_SECONDS: f64 = 86400.;
+const J1970: f64 = 2440588.;
+const J2000: f64 = 2451545.;
+const J0: f64 = 0.0009;
+const OBLIQUITY: f64 = 23.4397 * RAD;
+const SUN_DISTANCE_KM: f64 = 149598000.;
+
+/// Sun altitude at sunrise/sunset, accounting for refraction and the size of the disc
+const SUNRISE_ALTITUDE: f64 = -0.833;
+const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.;
+/// Moon altitude at moonrise/moonset, accounting for parallax and the size of the disc
+const MOONRISE_ALTITUDE: f64 = 0.133;
+
+/// Sun events for one day, `None` when the sun doesn't cross that altitude (polar day or night)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SunTimes {
+    pub civil_dawn: Option<DateTime<Utc>>,
+    pub sunrise: Option<DateTime<Utc>>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub civil_dusk: Option<DateTime<Utc>>,
+}
+
+/// Moon events within a day, either can be missing as the moon rises about 50 minutes later every day
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MoonTimes {
+    pub rise: Option<DateTime<Utc>>,
+    pub set: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum MoonPhaseName {
+    NewMoon,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    FullMoon,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct MoonPhase {
+    /// Position in the lunar cycle, 0 is new moon, 0.5 full moon
+    pub phase: f64,
+    /// Illuminated fraction of the disc, from 0 to 1
+    pub illumination: f64,
+}
+
+impl MoonPhase {
+    pub fn name(&self) -> MoonPhaseName {
+        match (self.phase * 8.).round() as usize % 8 {
+            0 => MoonPhaseName::NewMoon,
+            1 => MoonPhaseName::WaxingCrescent,
+            2 => MoonPhaseName::FirstQuarter,
+            3 => MoonPhaseName::WaxingGibbous,
+            4 => MoonPhaseName::FullMoon,
+            5 => MoonPhaseName::WaningGibbous,
+            6 => MoonPhaseName::LastQuarter,
+            _ => MoonPhaseName::WaningCrescent,
+        }
+    }
+
+    /// Spring tides (largest range) follow new and full moons, neap tides the quarters
+    pub fn is_spring_tide(&self) -> bool {
+        matches!(self.name(), MoonPhaseName::NewMoon | MoonPhaseName::FullMoon)
+    }
+}
+
+/// Everything the screens show about the sky for one local day
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DayAstronomy {
+    pub date: NaiveDate,
+    pub utc_offset: i32,
+    pub sun: SunTimes,
+    pub moon: MoonTimes,
+    pub moon_phase: MoonPhase,
+}
+
+impl DayAstronomy {
+    /// Sun and moon for the local `date` at a location whose clocks are `utc_offset` hours from UTC
+    pub fn new(date: NaiveDate, utc_offset: i32, lat: f64, lon: f64) -> Self {
+        let midnight = Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)) - Duration::hours(utc_offset as i64);
+        DayAstronomy {
+            date,
+            utc_offset,
+            sun: sun_times(date, lat, lon),
+            moon: moon_times(midnight, lat, lon),
+            moon_phase: moon_phase(midnight + Duration::hours(12)),
+        }
+    }
+}
+
+fn to_days(time: DateTime<Utc>) -> f64 {
+    time.timestamp_millis() as f64 / 1000. / DAY_SECONDS - 0.5 + J1970 - J2000
+}
+
+fn from_julian(julian: f64) -> Option<DateTime<Utc>> {
+    if !julian.is_finite() {
+        return None;
+    }
+    Utc.timestamp_millis_opt(((julian + 0.5 - J1970) * DAY_SECONDS * 1000.) as i64).single()
+}
+
+fn right_ascension(l: f64, b: f64) -> f64 {
+    (l.sin() * OBLIQUITY.cos() - b.tan() * OBLIQUITY.sin()).atan2(l.cos())
+}
+
+fn declination(l: f64, b: f64) -> f64 {
+    (b.sin() * OBLIQUITY.cos() + b.cos() * OBLIQUITY.sin() * l.sin()).asin()
+}
+
+fn altitude(hour_angle: f64, phi: f64, dec: f64) -> f64 {
+    (phi.sin() * dec.sin() + phi.cos() * dec.cos() * hour_angle.cos()).asin()
+}
+
+fn sidereal_time(days: f64, lw: f64) -> f64 {
+    RAD * (280.16 + 360.9856235 * days) - lw
+}
+
+fn astro_refraction(altitude: f64) -> f64 {
+    let altitude = altitude.max(0.);
+    0.0002967 / (altitude + 0.00312536 / (altitude + 0.08901179)).tan()
+}
+
+fn solar_mean_anomaly(days: f64) -> f64 {
+    RAD * (357.5291 + 0.98560028 * days)
+}
+
+fn ecliptic_longitude(mean_anomaly: f64) -> f64 {
+    let center = RAD
+        * (1.9148 * mean_anomaly.sin() + 0.02 * (2. * mean_anomaly).sin() + 0.0003 * (3. * mean_anomaly).sin());
+    let perihelion = RAD * 102.9372;
+    mean_anomaly + center + perihelion + PI
+}
+
+/// Right ascension and declination of the sun
+fn sun_coords(days: f64) -> (f64, f64) {
+    let l = ecliptic_longitude(solar_mean_anomaly(days));
+    (right_ascension(l, 0.), declination(l, 0.))
+}
+
+/// Right ascension, declination and distance in km of the moon
+fn moon_coords(days: f64) -> (f64, f64, f64) {
+    let longitude = RAD * (218.316 + 13.176396 * days);
+    let mean_anomaly = RAD * (134.963 + 13.064993 * days);
+    let argument_of_latitude = RAD * (93.272 + 13.229350 * days);
+    let l = longitude + RAD * 6.289 * mean_anomaly.sin();
+    let b = RAD * 5.128 * argument_of_latitude.sin();
+    let km = 385001. - 20905. * mean_anomaly.cos();
+    (right_ascension(l, b), declination(l, b), km)
+}
+
+/// Sunrise, sunset and civil twilight on `date` at the given location
+pub fn sun_times(date: NaiveDate, lat: f64, lon: f64) -> SunTimes {
+    let lw = RAD * -lon;
+    let phi = RAD * lat;
+
+    // start from the approximate solar noon so the nearest transit is the one on `date`
+    let noon = Utc.from_utc_datetime(&date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap()))
+        - Duration::seconds((lon / 15. * 3600.) as i64);
+    let cycle = (to_days(noon) - J0 - lw / (2. * PI)).round();
+    let approx_transit = |hour_angle: f64| J0 + (hour_angle + lw) / (2. * PI) + cycle;
+    let mean_anomaly = solar_mean_anomaly(approx_transit(0.));
+    let l = ecliptic_longitude(mean_anomaly);
+    let dec = declination(l, 0.);
+    let transit = |days: f64| J2000 + days + 0.0053 * mean_anomaly.sin() - 0.0069 * (2. * l).sin();
+    let solar_noon = transit(approx_transit(0.));
+
+    // acos is NaN when the sun never reaches `altitude`, which `from_julian` turns into None
+    let rise_and_set = |altitude: f64| {
+        let hour_angle = (((RAD * altitude).sin() - phi.sin() * dec.sin()) / (phi.cos() * dec.cos())).acos();
+        let set = transit(approx_transit(hour_angle));
+        (from_julian(solar_noon - (set - solar_noon)), from_julian(set))
+    };
+    let (sunrise, sunset) = rise_and_set(SUNRISE_ALTITUDE);
+    let (civil_dawn, civil_dusk) = rise_and_set(CIVIL_TWILIGHT_ALTITUDE);
+    SunTimes {
+        civil_dawn,
+        sunrise,
+        sunset,
+        civil_dusk,
+    }
+}
+
+/// Moon phase and illumination at `time`
+pub fn moon_phase(time: DateTime<Utc>) -> MoonPhase {
+    let days = to_days(time);
+    let (sun_ra, sun_dec) = sun_coords(days);
+    let (moon_ra, moon_dec, moon_distance) = moon_coords(days);
+
+    let elongation = (sun_dec.sin() * moon_dec.sin() + sun_dec.cos() * moon_dec.cos() * (sun_ra - moon_ra).cos()).acos();
+    let inclination = (SUN_DISTANCE_KM * elongation.sin()).atan2(moon_distance - SUN_DISTANCE_KM * elongation.cos());
+    let angle = (sun_dec.cos() * (sun_ra - moon_ra).sin())
+        .atan2(sun_dec.sin() * moon_dec.cos() - sun_dec.cos() * moon_dec.sin() * (sun_ra - moon_ra).cos());
+
+    MoonPhase {
+        phase: 0.5 + 0.5 * inclination * angle.signum() / PI,
+        illumination: (1. + inclination.cos()) / 2.,
+    }
+}
+
+fn moon_altitude(time: DateTime<Utc>, lat: f64, lon: f64) -> f64 {
+    let days = to_days(time);
+    let (ra, dec, _) = moon_coords(days);
+    let altitude = altitude(sidereal_time(days, RAD * -lon) - ra, RAD * lat, dec);
+    altitude + astro_refraction(altitude)
+}
+
+/// Moonrise and moonset in the 24 hours from `start`
+pub fn moon_times(start: DateTime<Utc>, lat: f64, lon: f64) -> MoonTimes {
+    let horizon = MOONRISE_ALTITUDE * RAD;
+    let altitude_at = |hours: i64| moon_altitude(start + Duration::hours(hours), lat, lon) - horizon;
+    let at_hours = |hours: f64| Some(start + Duration::seconds((hours * 3600.) as i64));
+
+    let mut rise = None;
+    let mut set = None;
+    let mut h0 = altitude_at(0);
+
+    // fit a parabola through every three hourly altitudes and find where it crosses the horizon
+    for i in (1..=23).step_by(2) {
+        let h1 = altitude_at(i);
+        let h2 = altitude_at(i + 1);
+        let a = (h0 + h2) / 2. - h1;
+        let b = (h2 - h0) / 2.;
+        let x_extreme = -b / (2. * a);
+        let y_extreme = (a * x_extreme + b) * x_extreme + h1;
+        let discriminant = b * b - 4. * a * h1;
+
+        let mut roots = 0;
+        let (mut x1, mut x2) = (0., 0.);
+        if discriminant >= 0. {
+            let dx = discriminant.sqrt() / (a.abs() * 2.);
+            x1 = x_extreme - dx;
+            x2 = x_extreme + dx;
+            if x1.abs() <= 1. {
+                roots += 1;
+            }
+            if x2.abs() <= 1. {
+                roots += 1;
+            }
+            if x1 < -1. {
+                x1 = x2;
+            }
+        }
+
+        let i = i as f64;
+        if roots == 1 {
+            if h0 < 0. {
+                rise = at_hours(i + x1);
+            } else {
+                set = at_hours(i + x1);
+            }
+        } else if roots == 2 {
+            rise = at_hours(i + if y_extreme < 0. { x2 } else { x1 });
+            set = at_hours(i + if y_extreme < 0. { x1 } else { x2 });
+        }
+
+        if rise.is_some() && set.is_some() {
+            break;
+        }
+        h0 = h2;
+    }
+    MoonTimes { rise, set }
+}
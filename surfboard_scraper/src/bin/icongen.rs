@@ -0,0 +1,124 @@
+// Converts a directory of PNG/SVG icons into `ImageRaw<BinaryColor>` constants, see `make icons`.
+//
+//   icongen <icon dir> [--size <px>] [--threshold <0-255>] [--dither] [--output <file.rs>]
+
+use anyhow::{Context, Result, anyhow};
+use image::{DynamicImage, RgbaImage};
+use std::fs;
+use std::path::{Path, PathBuf};
+use surfboard_scraper::common::icon::{IconBitmap, const_name};
+
+const ICON_EXTENSIONS: [&str; 2] = ["png", "svg"];
+const DEFAULT_THRESHOLD: u8 = 128;
+
+struct Options {
+    icon_dir: PathBuf,
+    size: Option<u32>,
+    threshold: u8,
+    dither: bool,
+    output: Option<PathBuf>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options> {
+    let mut options = Options {
+        icon_dir: PathBuf::new(),
+        size: None,
+        threshold: DEFAULT_THRESHOLD,
+        dither: false,
+        output: None,
+    };
+    let mut icon_dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(anyhow!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--size" => options.size = Some(value()?.parse().context("Invalid --size")?),
+            "--threshold" => options.threshold = value()?.parse().context("Invalid --threshold")?,
+            "--dither" => options.dither = true,
+            "--output" => options.output = Some(value()?.into()),
+            _ if arg.starts_with("--") => return Err(anyhow!("Unknown option {}", arg)),
+            _ => icon_dir = Some(PathBuf::from(arg)),
+        }
+    }
+    options.icon_dir = icon_dir.ok_or(anyhow!(
+        "Usage: icongen <icon dir> [--size <px>] [--threshold <0-255>] [--dither] [--output <file.rs>]"
+    ))?;
+    Ok(options)
+}
+
+/// Render an SVG at `size` pixels on its longer side, or at its own size
+fn load_svg(path: &Path, size: Option<u32>) -> Result<DynamicImage> {
+    let tree = resvg::usvg::Tree::from_data(&fs::read(path)?, &resvg::usvg::Options::default())?;
+    let tree_size = tree.size();
+    let scale = size.map_or(1., |size| size as f32 / tree_size.width().max(tree_size.height()));
+    let (width, height) = (
+        (tree_size.width() * scale).ceil() as u32,
+        (tree_size.height() * scale).ceil() as u32,
+    );
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height).ok_or(anyhow!("Empty SVG {:?}", path))?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    // tiny-skia stores premultiplied alpha
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    let image = RgbaImage::from_raw(width, height, pixels).ok_or(anyhow!("Failed to read SVG {:?}", path))?;
+    Ok(DynamicImage::ImageRgba8(image))
+}
+
+fn generate(options: &Options, command: &str) -> Result<String> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(&options.icon_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    paths.retain(|path| {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| ICON_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+    });
+    // sorted so the output only changes when the icons do
+    paths.sort();
+
+    let mut source = format!(
+        "// Written by `{}` (src/bin/icongen.rs), edit the icons and rerun instead of editing this file\n\n\
+         use embedded_graphics::image::ImageRaw;\nuse embedded_graphics::pixelcolor::BinaryColor;\n",
+        command
+    );
+    for path in paths {
+        let image = if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"))
+        {
+            load_svg(&path, options.size)?
+        } else {
+            image::open(&path).with_context(|| format!("Failed to open {:?}", path))?
+        };
+        let icon = IconBitmap::from_image(&image, options.size, options.threshold, options.dither);
+        let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        source.push('\n');
+        source.push_str(&icon.to_rust_const(&const_name(stem)));
+    }
+    Ok(source)
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = parse_args(&args)?;
+    let command = std::iter::once("icongen".to_string())
+        .chain(args)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let source = generate(&options, &command)?;
+    match &options.output {
+        Some(output) => fs::write(output, source)?,
+        None => print!("{}", source),
+    }
+    Ok(())
+}
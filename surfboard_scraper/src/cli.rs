@@ -0,0 +1,278 @@
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use clap::{Args, Parser, Subcommand};
+use glob::glob;
+use std::{
+    collections::HashSet,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+use tracing::{error, info};
+
+use crate::{
+    device_config::Configuration,
+    http::DataSource,
+    logging::LogFormat,
+    metrics::DEFAULT_METRICS_PORT,
+    preview::PreviewOptions,
+    screen::{RenderContext, SERVER_SIDE_IMAGE_BYTES},
+    serve::ServeOptions,
+    versions::versioned_key,
+};
+
+pub const CONFIG_DIRECTORY: &str = "deploy/configs";
+pub const STATE_FILE: &str = "scraper_state.json";
+const DEFAULT_PORT: u16 = 8080;
+
+// limits of the firmware's `heapless` configuration, see surfboard/src/task/state.rs
+const FIRMWARE_MAX_SCREENS: usize = 5;
+const FIRMWARE_MAX_URL_LENGTH: usize = 128;
+
+/// Renders surf reports and other screens for the e-paper devices and publishes them
+#[derive(Debug, Parser)]
+#[command(name = "surfboard_scraper")]
+pub struct Cli {
+    /// Directory of the device configs
+    #[arg(long, global = true, default_value = CONFIG_DIRECTORY)]
+    pub config_directory: PathBuf,
+
+    /// What publishing remembers between runs, when versions no config points at were retired. Outside the config
+    /// directory, which is watched for edits
+    #[arg(long, global = true, default_value = STATE_FILE)]
+    pub state_file: PathBuf,
+
+    /// Port `/metrics` is served on while publishing continuously
+    #[arg(long, global = true, default_value_t = DEFAULT_METRICS_PORT)]
+    pub metrics_port: u16,
+
+    /// Log level or `RUST_LOG` directives, `RUST_LOG` wins when set
+    #[arg(long, global = true, default_value = "info")]
+    pub log_level: String,
+
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+
+    /// What to do, `daemon` when omitted
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Render one screen of a device config to a PNG or QOI file
+    Render(RenderArgs),
+    /// Check every device config, exits with an error when one has problems
+    Validate,
+    /// Publish every device config, once with `--once` or as their refresh policies come due
+    Publish(PublishArgs),
+    /// Publish as refresh policies come due until SIGTERM
+    Daemon,
+    /// Serve the device configs' screens as PNGs on localhost instead of publishing them
+    Preview(PreviewArgs),
+    /// Publish as `daemon` does and serve a "directory" publish target to the devices
+    Serve(ServeArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+    /// Device config, a path or a file in the config directory
+    #[arg(long)]
+    pub config: PathBuf,
+    /// `id` of the screen or screen saver
+    #[arg(long)]
+    pub screen: String,
+    /// Output file, `.png`, or `.qoi` or `.epd` for the bytes the screen publishes in its `format`
+    #[arg(long)]
+    pub out: PathBuf,
+    #[command(flatten)]
+    pub data: DataArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct PublishArgs {
+    /// Publish every screen once and exit, for running from cron
+    #[arg(long)]
+    pub once: bool,
+    /// Render every screen and report its size without uploading anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PreviewArgs {
+    #[arg(long, default_value_t = DEFAULT_PORT)]
+    pub port: u16,
+    #[command(flatten)]
+    pub data: DataArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Port on every interface, devices connect over the local network
+    #[arg(long, default_value_t = DEFAULT_PORT)]
+    pub port: u16,
+    /// The `path` of the devices' "directory" publish targets
+    #[arg(long, default_value = "public")]
+    pub directory: PathBuf,
+}
+
+/// Where screens get their data and what time they're drawn at
+#[derive(Debug, Args)]
+pub struct DataArgs {
+    /// Render from saved Surfline responses in this directory instead of the API
+    #[arg(long)]
+    pub fixtures: Option<PathBuf>,
+    /// Draw the screens as of this RFC 3339 time instead of now
+    #[arg(long)]
+    pub time: Option<DateTime<Utc>>,
+}
+
+impl DataArgs {
+    fn render_context(&self, config: &Configuration) -> RenderContext {
+        RenderContext {
+            data_source: match &self.fixtures {
+                Some(fixtures) => DataSource::Fixtures(fixtures.clone()),
+                None => DataSource::Live,
+            },
+            frozen_time: self.time,
+            ..config.render_context()
+        }
+    }
+}
+
+impl PreviewArgs {
+    pub fn into_options(self, config_directory: PathBuf) -> PreviewOptions {
+        PreviewOptions {
+            config_directory,
+            address: SocketAddr::from(([127, 0, 0, 1], self.port)),
+            fixtures: self.data.fixtures,
+            frozen_time: self.data.time,
+        }
+    }
+}
+
+impl ServeArgs {
+    pub fn into_options(self) -> ServeOptions {
+        ServeOptions {
+            directory: self.directory,
+            address: SocketAddr::from(([0, 0, 0, 0], self.port)),
+        }
+    }
+}
+
+fn read_config(path: &Path) -> Result<Configuration> {
+    let json = std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    serde_json::from_str(&json).with_context(|| format!("Failed to parse {:?}", path))
+}
+
+fn config_paths(config_directory: &Path) -> Result<Vec<PathBuf>> {
+    let pattern = config_directory.join("*.json");
+    let paths = glob(&pattern.to_string_lossy())?.collect::<Result<Vec<PathBuf>, _>>()?;
+    Ok(paths)
+}
+
+/// Render one screen of a device config to `args.out`, a PNG by its extension or otherwise exactly what publishing
+/// would upload
+pub async fn render(config_directory: &Path, args: &RenderArgs) -> Result<()> {
+    let path = match args.config.exists() {
+        true => args.config.clone(),
+        false => config_directory.join(&args.config),
+    };
+    let config = read_config(&path)?;
+    let screen = config
+        .all_screens()
+        .find(|screen| screen.id == args.screen)
+        .ok_or(anyhow!("No screen {} in {:?}", args.screen, path))?;
+    let context = args.data.render_context(&config);
+    match args.out.extension().and_then(|extension| extension.to_str()) {
+        Some("png") => screen.draw_to_png(&context, &args.out.to_string_lossy()).await?,
+        // in the screen's format and checked against the device's buffer, as published
+        Some("qoi" | "epd") => {
+            let bytes = screen.encode(&screen.draw_to_display(&context).await?)?;
+            std::fs::write(&args.out, bytes).with_context(|| format!("Failed to write {:?}", args.out))?;
+        }
+        _ => return Err(anyhow!("Unsupported output {:?}, use .png, .qoi or .epd", args.out)),
+    }
+    info!(screen = screen.id, out = ?args.out, "Rendered");
+    Ok(())
+}
+
+/// Problems with the device configs in `config_directory` that would stop them publishing or loading on a device
+pub fn validate(config_directory: &Path) -> Result<Vec<String>> {
+    let mut problems = Vec::new();
+    let paths = config_paths(config_directory)?;
+    if paths.is_empty() {
+        problems.push(format!("No device configs in {:?}", config_directory));
+    }
+    for path in paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let config = match read_config(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                problems.push(format!("{}: {:#}", name, e));
+                continue;
+            }
+        };
+        let Some(publish) = &config.publish else {
+            problems.push(format!("{}: no \"publish\" target", name));
+            continue;
+        };
+        let mut ids = HashSet::new();
+        for screen in config.all_screens() {
+            if !ids.insert(&screen.id) {
+                problems.push(format!("{}: screen id {} is used twice", name, screen.id));
+            }
+            match publish.key(&screen.url) {
+                Ok(key) => {
+                    let url = format!("{}{}", publish.public_url(), versioned_key(&key, &"0".repeat(32)));
+                    if url.len() > FIRMWARE_MAX_URL_LENGTH {
+                        problems.push(format!(
+                            "{}: {} is longer than the firmware's {} characters once versioned",
+                            name, screen.url, FIRMWARE_MAX_URL_LENGTH
+                        ));
+                    }
+                }
+                Err(e) => problems.push(format!("{}: {:#}", name, e)),
+            }
+        }
+        // the busiest day in the coming year
+        let today = Local::now().date_naive();
+        let most_screens = (0..366)
+            .map(|days| config.active_on(today + TimeDelta::days(days)).screens.len())
+            .max()
+            .unwrap_or_default();
+        if most_screens > FIRMWARE_MAX_SCREENS {
+            problems.push(format!(
+                "{}: {} screens on one day, the firmware holds {}",
+                name, most_screens, FIRMWARE_MAX_SCREENS
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+/// Render today's screens of every device config and report their sizes without uploading them
+pub async fn dry_run(config_directory: &Path) -> Result<()> {
+    for path in config_paths(config_directory)? {
+        let config = read_config(&path)?.active_on(Local::now().date_naive());
+        let context = config.render_context();
+        for screen in config.all_screens() {
+            let encoded = match screen.draw_to_display(&context).await {
+                Ok(display) => screen.encode(&display),
+                Err(e) => Err(e),
+            };
+            match encoded {
+                Ok(bytes) => info!(
+                    device = ?path,
+                    screen = screen.id,
+                    bytes = bytes.len(),
+                    max_bytes = SERVER_SIDE_IMAGE_BYTES,
+                    format = ?screen.format,
+                    "Rendered"
+                ),
+                Err(e) => error!(device = ?path, screen = screen.id, "Failed to render: {:#}", e),
+            }
+        }
+    }
+    Ok(())
+}
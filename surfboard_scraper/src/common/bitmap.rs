@@ -0,0 +1,160 @@
+use anyhow::Result;
+use core::fmt::Debug;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use epd_waveshare::color::TriColor;
+use image::RgbImage;
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+
+const LUMINANCE_THRESHOLD: u8 = 128;
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// Tri-color raster image, stored one byte per pixel so it can be serialized with the screen data
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TriColorBitmap {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<u8>,
+}
+
+impl TriColorBitmap {
+    /// Load an image from disk, scale it to fit within the given box and threshold it to black/white
+    pub fn load_thresholded(path: &str, max_width: u32, max_height: u32) -> Result<Self> {
+        let image = image::open(path)?;
+        let image = if image.width() > max_width || image.height() > max_height {
+            image.resize(max_width, max_height, FilterType::Triangle)
+        } else {
+            image
+        };
+        let rgba = image.to_rgba8();
+        let luma = image.to_luma8();
+        let pixels = rgba
+            .pixels()
+            .zip(luma.pixels())
+            .map(|(rgba, luma)| {
+                if rgba.0[3] < ALPHA_THRESHOLD || luma.0[0] >= LUMINANCE_THRESHOLD {
+                    color_to_byte(TriColor::White)
+                } else {
+                    color_to_byte(TriColor::Black)
+                }
+            })
+            .collect();
+        Ok(TriColorBitmap {
+            width: rgba.width(),
+            height: rgba.height(),
+            pixels,
+        })
+    }
+
+    /// Floyd–Steinberg error-diffusion dither of an RGB image to the panel's tri-color palette.
+    /// `strength` scales the diffused error, 1.0 is a classic dither and 0.0 a plain threshold.
+    pub fn dithered(image: &RgbImage, use_chromatic: bool, strength: f32) -> Self {
+        let palette: &[TriColor] = if use_chromatic {
+            &[TriColor::White, TriColor::Black, TriColor::Chromatic]
+        } else {
+            &[TriColor::White, TriColor::Black]
+        };
+        let palette_rgb: Vec<[f32; 3]> = palette
+            .iter()
+            .map(|color| {
+                let rgb = Rgb888::from(*color);
+                [rgb.r() as f32, rgb.g() as f32, rgb.b() as f32]
+            })
+            .collect();
+
+        let (width, height) = image.dimensions();
+        let mut working: Vec<[f32; 3]> = image
+            .pixels()
+            .map(|p| [p.0[0] as f32, p.0[1] as f32, p.0[2] as f32])
+            .collect();
+        let mut pixels = vec![0u8; (width * height) as usize];
+
+        for y in 0..height {
+            for x in 0..width {
+                let idx = (y * width + x) as usize;
+                let old = working[idx];
+                let (nearest, new) = palette_rgb
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| color_distance(&old, a).total_cmp(&color_distance(&old, b)))
+                    .unwrap();
+                pixels[idx] = color_to_byte(palette[nearest]);
+
+                let error = [
+                    (old[0] - new[0]) * strength,
+                    (old[1] - new[1]) * strength,
+                    (old[2] - new[2]) * strength,
+                ];
+                let mut diffuse = |dx: i32, dy: u32, weight: f32| {
+                    let nx = x as i32 + dx;
+                    let ny = y + dy;
+                    if nx >= 0 && (nx as u32) < width && ny < height {
+                        let target = &mut working[(ny * width + nx as u32) as usize];
+                        for (channel, error) in target.iter_mut().zip(error) {
+                            *channel += error * weight;
+                        }
+                    }
+                };
+                diffuse(1, 0, 7. / 16.);
+                diffuse(-1, 1, 3. / 16.);
+                diffuse(0, 1, 5. / 16.);
+                diffuse(1, 1, 1. / 16.);
+            }
+        }
+
+        TriColorBitmap { width, height, pixels }
+    }
+
+    /// Nearest-neighbour enlargement by an integer factor
+    pub fn upscaled(&self, factor: u32) -> Self {
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.pixels[(y * self.width + x) as usize])
+            .collect();
+        TriColorBitmap { width, height, pixels }
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> TriColor {
+        byte_to_color(self.pixels[(y * self.width + x) as usize])
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    pub fn draw<D, E>(&self, top_left: Point, target: &mut D) -> Result<(), E>
+    where
+        E: Debug,
+        D: DrawTarget<Color = TriColor, Error = E>,
+    {
+        let width = self.width;
+        target.draw_iter(self.pixels.iter().enumerate().map(|(i, color)| {
+            let position = Point::new((i as u32 % width) as i32, (i as u32 / width) as i32);
+            Pixel(top_left + position, byte_to_color(*color))
+        }))
+    }
+}
+
+fn color_distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    // weight channels by their contribution to perceived brightness
+    0.299 * (a[0] - b[0]).powi(2) + 0.587 * (a[1] - b[1]).powi(2) + 0.114 * (a[2] - b[2]).powi(2)
+}
+
+fn color_to_byte(color: TriColor) -> u8 {
+    match color {
+        TriColor::White => 0,
+        TriColor::Black => 1,
+        TriColor::Chromatic => 2,
+    }
+}
+
+fn byte_to_color(byte: u8) -> TriColor {
+    match byte {
+        1 => TriColor::Black,
+        2 => TriColor::Chromatic,
+        _ => TriColor::White,
+    }
+}
@@ -1,21 +1,28 @@
-use chrono::{Datelike, FixedOffset, NaiveDateTime, TimeZone, Timelike, Utc};
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Timelike, Utc};
 use core::fmt::Debug;
 use core::fmt::Write;
 use embedded_graphics::image::GetPixel;
 use embedded_graphics::image::ImageRaw;
-use embedded_graphics::mono_font::ascii::FONT_6X10;
-use embedded_graphics::mono_font::ascii::{FONT_8X13, FONT_9X15};
-use embedded_graphics::mono_font::iso_8859_16::FONT_5X8;
+use embedded_graphics::mono_font::iso_8859_1::{FONT_5X8, FONT_8X13, FONT_9X15, FONT_10X20};
 use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
 use embedded_graphics::{
     mono_font::MonoTextStyle,
     prelude::*,
     text::{Alignment, LineHeight, Text, TextStyleBuilder},
 };
 use epd_waveshare::color::TriColor;
+use qrcode::{EcLevel, QrCode};
 
-use crate::image_data::{MIST, MOSTLY_CLEAR, MOSTLY_CLOUDY, SHOWERS, WEATHER_SUNNY};
-use crate::surfline_types::weather::WeatherCondition;
+use crate::astronomy::MoonPhase;
+use crate::locale::Messages;
+use crate::image_data::{
+    CLOUDY, HAZE, MIST, MOSTLY_CLEAR, MOSTLY_CLOUDY, NIGHT_CLEAR, NIGHT_MOSTLY_CLEAR, NIGHT_PARTLY_CLOUDY, NIGHT_SHOWERS,
+    PARTLY_CLOUDY, RAIN, SCATTERED_SHOWERS, SHOWERS, SNOW, THUNDERSTORM, WEATHER_SUNNY, WIND,
+};
+use crate::surfline_types::report::WaterTemperature;
+use crate::surfline_types::weather::{WeatherCondition, WeatherKind};
 
 /// Common function to draw binary images on tri-color displays
 pub fn draw_binary_image_on_tricolor<D>(raw_image: &ImageRaw<BinaryColor>, top_left: Point, target: &mut D)
@@ -39,29 +46,60 @@ pub fn get_local_time_from_unix(unix_timestamp: i64, offset: i32) -> NaiveDateTi
     offset.from_local_datetime(&time).unwrap().naive_utc()
 }
 
+/// Format a time of day in the spot's local time (e.g., "6:58"), or "--" when there is none
+pub fn format_local_time(messages: &Messages, time: Option<DateTime<Utc>>, utc_offset: i32) -> String {
+    match time {
+        Some(time) => {
+            let local_time = get_local_time_from_unix(time.timestamp(), utc_offset);
+            messages.format_time(local_time.hour(), local_time.minute())
+        }
+        None => "--".into(),
+    }
+}
+
+/// Draw the moon as it looks in the sky, shadow in black and the waxing moon lit from the right
+pub fn draw_moon_phase<D, E>(target: &mut D, moon_phase: &MoonPhase, center: Point, radius: i32) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    let phase = moon_phase.phase;
+    let pixels = (-radius..=radius)
+        .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+        .filter(|(dx, dy)| dx * dx + dy * dy <= radius * radius)
+        .map(|(dx, dy)| {
+            // the terminator is an ellipse whose half-width follows the cosine of the phase angle
+            let half_width = ((radius * radius - dy * dy) as f64).sqrt();
+            let terminator = (2. * std::f64::consts::PI * phase).cos() * half_width;
+            let lit = if phase <= 0.5 {
+                dx as f64 > terminator
+            } else {
+                (dx as f64) < -terminator
+            };
+            Pixel(center + Point::new(dx, dy), if lit { TriColor::White } else { TriColor::Black })
+        });
+    target.draw_iter(pixels)?;
+    Circle::with_center(center, (2 * radius + 1) as u32)
+        .into_styled(PrimitiveStyle::with_stroke(TriColor::Black, 1))
+        .draw(target)?;
+    Ok(())
+}
+
 /// Common function to draw the last updated timestamp
-pub fn draw_last_updated<D, E>(target: &mut D, last_updated: &NaiveDateTime) -> Result<(), E>
+pub fn draw_last_updated<D, E>(target: &mut D, messages: &Messages, last_updated: &NaiveDateTime) -> Result<(), E>
 where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
+    // right-aligned so longer translations grow into the screen rather than off its edge
     let text_style = TextStyleBuilder::new()
-        .alignment(Alignment::Left)
+        .alignment(Alignment::Right)
         .line_height(LineHeight::Percent(100))
         .build();
-    let mut txt: String = String::new();
-    write!(
-        txt,
-        "Updated: {}/{} {:02}:{:02}",
-        last_updated.month(),
-        last_updated.day(),
-        last_updated.hour(),
-        last_updated.minute()
-    )
-    .expect("Failed to write");
+    let txt = messages.format_updated(last_updated);
     Text::with_text_style(
         txt.as_str(),
-        Point::new(680, 470),
+        Point::new(795, 470),
         MonoTextStyle::new(&FONT_5X8, TriColor::Black),
         text_style,
     )
@@ -69,57 +107,57 @@ where
     Ok(())
 }
 
+/// Icon for a weather condition, clear and partly cloudy nights get a moon instead of the sun
+pub fn weather_icon(condition: &WeatherCondition) -> Option<&'static ImageRaw<'static, BinaryColor>> {
+    let (kind, night) = match condition {
+        WeatherCondition::Day(kind) => (kind, false),
+        WeatherCondition::Night(kind) => (kind, true),
+        WeatherCondition::Unknown(_) => return None,
+    };
+    let icon = match kind {
+        WeatherKind::Clear if night => &NIGHT_CLEAR,
+        WeatherKind::Clear => &WEATHER_SUNNY,
+        WeatherKind::MostlyClear if night => &NIGHT_MOSTLY_CLEAR,
+        WeatherKind::MostlyClear => &MOSTLY_CLEAR,
+        WeatherKind::PartlyCloudy | WeatherKind::MostlyCloudy if night => &NIGHT_PARTLY_CLOUDY,
+        WeatherKind::PartlyCloudy => &PARTLY_CLOUDY,
+        WeatherKind::MostlyCloudy => &MOSTLY_CLOUDY,
+        WeatherKind::Cloudy | WeatherKind::Overcast => &CLOUDY,
+        WeatherKind::Haze | WeatherKind::Smoke if !night => &HAZE,
+        WeatherKind::Fog | WeatherKind::Mist | WeatherKind::Haze | WeatherKind::Smoke => &MIST,
+        WeatherKind::BriefShowers | WeatherKind::BriefShowersPossible if night => &NIGHT_SHOWERS,
+        WeatherKind::BriefShowers | WeatherKind::BriefShowersPossible => &SCATTERED_SHOWERS,
+        WeatherKind::Drizzle | WeatherKind::LightShowers | WeatherKind::LightRain | WeatherKind::Showers => &SHOWERS,
+        WeatherKind::Rain | WeatherKind::HeavyRain | WeatherKind::HeavyShowers => &RAIN,
+        WeatherKind::ThunderShowers | WeatherKind::ThunderStorms | WeatherKind::BriefThunderStorms => &THUNDERSTORM,
+        WeatherKind::LightSnow
+        | WeatherKind::Snow
+        | WeatherKind::HeavySnow
+        | WeatherKind::SnowShowers
+        | WeatherKind::BriefSnowShowers
+        | WeatherKind::BriefSnowShowersPossible
+        | WeatherKind::LightSnowShowers
+        | WeatherKind::HeavySnowShowers
+        | WeatherKind::RainAndSnow
+        | WeatherKind::Sleet => &SNOW,
+        WeatherKind::Windy => &WIND,
+    };
+    Some(icon)
+}
+
 /// Common function to draw weather condition icons
 pub fn draw_weather_icon<D>(condition: &WeatherCondition, position: Point, target: &mut D)
 where
     D: DrawTarget<Color = TriColor>,
 {
-    match condition {
-        WeatherCondition::NightClear | WeatherCondition::Clear => {
-            draw_binary_image_on_tricolor(
-                &ImageRaw::<BinaryColor>::new(WEATHER_SUNNY, 32),
-                Point::new(position.x - 16, position.y - 16),
-                target,
-            );
-        }
-        WeatherCondition::MostlyClear | WeatherCondition::NightMostlyClear => {
-            draw_binary_image_on_tricolor(
-                &ImageRaw::<BinaryColor>::new(MOSTLY_CLEAR, 32),
-                Point::new(position.x - 16, position.y - 16),
-                target,
-            );
-        }
-        WeatherCondition::NightMostlyCloudy | WeatherCondition::MostlyCloudy | WeatherCondition::NightCloudy => {
-            draw_binary_image_on_tricolor(
-                &ImageRaw::<BinaryColor>::new(MOSTLY_CLOUDY, 32),
-                Point::new(position.x - 16, position.y - 16),
-                target,
-            );
-        }
-        WeatherCondition::Mist | WeatherCondition::NightMist | WeatherCondition::NightFog => {
-            draw_binary_image_on_tricolor(
-                &ImageRaw::<BinaryColor>::new(MIST, 32),
-                Point::new(position.x - 16, position.y - 16),
-                target,
-            );
-        }
-        WeatherCondition::NightBriefShowers
-        | WeatherCondition::BriefShowers
-        | WeatherCondition::BriefShowersPossible
-        | WeatherCondition::NightDrizzle
-        | WeatherCondition::Drizzle => {
-            draw_binary_image_on_tricolor(
-                &ImageRaw::<BinaryColor>::new(SHOWERS, 32),
-                Point::new(position.x - 16, position.y - 16),
-                target,
-            );
-        }
-        WeatherCondition::Unknown(condition_str) => {
-            // Display the unknown condition text instead of an icon
+    match weather_icon(condition) {
+        Some(icon) => draw_binary_image_on_tricolor(icon, Point::new(position.x - 16, position.y - 16), target),
+        None => {
+            // unknown conditions are logged when parsed, a placeholder keeps the raw text from overlapping neighbours
             let _ = Text::with_text_style(
-                condition_str,
-                position,
-                MonoTextStyle::new(&FONT_6X10, TriColor::Black),
+                "?",
+                position + Point::new(0, 7),
+                MonoTextStyle::new(&FONT_10X20, TriColor::Black),
                 centered_text_style(),
             )
             .draw(target);
@@ -144,16 +182,16 @@ pub fn left_text_style() -> embedded_graphics::text::TextStyle {
 }
 
 /// Format wave height range as a string (e.g., "2-4ft")
-pub fn format_wave_height(min_height: i32, max_height: i32) -> String {
+pub fn format_wave_height(messages: &Messages, min_height: i32, max_height: i32) -> String {
     let mut height_text = String::new();
-    write!(height_text, "{}-{}ft", min_height, max_height).unwrap();
+    write!(height_text, "{}-{}{}", min_height, max_height, messages.feet).unwrap();
     height_text
 }
 
 /// Format wind speed as a string (e.g., "12kt")
-pub fn format_wind_speed(speed: f32) -> String {
+pub fn format_wind_speed(messages: &Messages, speed: f32) -> String {
     let mut speed_text = String::new();
-    write!(speed_text, "{:.0}kt", speed).unwrap();
+    write!(speed_text, "{:.0}{}", speed, messages.knots).unwrap();
     speed_text
 }
 
@@ -164,6 +202,66 @@ pub fn format_temperature_range(min_temp: f32, max_temp: f32) -> String {
     temp_text
 }
 
+/// Format water temperature and suggested wetsuit (e.g., "Water 55-57°F 4/3mm"), "Water no data" without a report
+pub fn format_water_temperature(
+    messages: &Messages,
+    water_temperature: Option<&WaterTemperature>,
+    wetsuit: Option<&str>,
+) -> String {
+    let Some(water_temperature) = water_temperature else {
+        return format!("{} {}", messages.water, messages.no_data);
+    };
+    let mut water_text = String::new();
+    write!(
+        water_text,
+        "{} {}°{}",
+        messages.water,
+        format_temperature_range(water_temperature.min, water_temperature.max),
+        water_temperature.unit
+    )
+    .unwrap();
+    if let Some(wetsuit) = wetsuit {
+        write!(water_text, " {}", wetsuit).unwrap();
+    }
+    water_text
+}
+
+/// Draw the water temperature header next to the spot name
+pub fn draw_water_temperature<D, E>(
+    target: &mut D,
+    messages: &Messages,
+    water_temperature: Option<&WaterTemperature>,
+    wetsuit: Option<&str>,
+    position: Point,
+) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    Text::with_text_style(
+        format_water_temperature(messages, water_temperature, wetsuit).as_str(),
+        position,
+        MonoTextStyle::new(&FONT_10X20, TriColor::Chromatic),
+        left_text_style(),
+    )
+    .draw(target)?;
+    Ok(())
+}
+
+/// Draw the placeholder for a section whose data could not be fetched
+pub fn draw_no_data<D, E>(
+    target: &mut D,
+    messages: &Messages,
+    position: Point,
+    style: embedded_graphics::text::TextStyle,
+) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    draw_text(target, messages.no_data, position, style)
+}
+
 /// Draw text with standard font and color
 pub fn draw_text<D, E>(
     target: &mut D,
@@ -193,3 +291,123 @@ where
     Text::with_text_style(text, position, MonoTextStyle::new(&FONT_8X13, TriColor::Black), style).draw(target)?;
     Ok(())
 }
+
+/// Split text into lines of at most `max_chars` characters, breaking on whitespace and honoring explicit newlines
+pub fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut lines: Vec<String> = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+            // hard-break words that would never fit on a line by themselves
+            while word.chars().count() > max_chars {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                let split_at = word.char_indices().nth(max_chars).map(|(i, _)| i).unwrap_or(word.len());
+                lines.push(word[..split_at].to_string());
+                word = &word[split_at..];
+            }
+            if word.is_empty() {
+                continue;
+            }
+            if line.is_empty() {
+                line.push_str(word);
+            } else if line.chars().count() + 1 + word.chars().count() <= max_chars {
+                line.push(' ');
+                line.push_str(word);
+            } else {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Draw target adapter that magnifies everything drawn through it by an integer factor around `origin`
+pub struct ScaledDrawTarget<'a, D> {
+    target: &'a mut D,
+    origin: Point,
+    scale: u32,
+}
+
+impl<'a, D> ScaledDrawTarget<'a, D> {
+    pub fn new(target: &'a mut D, origin: Point, scale: u32) -> Self {
+        ScaledDrawTarget {
+            target,
+            origin,
+            scale: scale.max(1),
+        }
+    }
+}
+
+impl<D> OriginDimensions for ScaledDrawTarget<'_, D>
+where
+    D: DrawTarget<Color = TriColor>,
+{
+    fn size(&self) -> Size {
+        self.target.bounding_box().size
+    }
+}
+
+impl<D> DrawTarget for ScaledDrawTarget<'_, D>
+where
+    D: DrawTarget<Color = TriColor>,
+{
+    type Color = TriColor;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let scale = self.scale as i32;
+        let origin = self.origin;
+        self.target.draw_iter(pixels.into_iter().flat_map(|Pixel(p, color)| {
+            let top_left = origin + (p - origin) * scale;
+            (0..scale * scale).map(move |i| Pixel(top_left + Point::new(i % scale, i / scale), color))
+        }))
+    }
+}
+
+/// Modules of white border around a QR code, narrower than the spec's 4 since the panel background is white anyway
+const QR_QUIET_ZONE: u32 = 2;
+
+fn encode_qr_code(data: &str) -> Result<QrCode> {
+    Ok(QrCode::with_error_correction_level(data, EcLevel::L)?)
+}
+
+/// Side length in pixels of `data` drawn with `draw_qr_code`, fails if it is too long for a QR code
+pub fn qr_code_size(data: &str, module_size: u32) -> Result<u32> {
+    Ok((encode_qr_code(data)?.width() as u32 + 2 * QR_QUIET_ZONE) * module_size)
+}
+
+/// Draw `data` as a QR code of `module_size` pixel modules, check it fits with `qr_code_size` first, nothing is
+/// drawn when it doesn't
+pub fn draw_qr_code<D, E>(target: &mut D, data: &str, top_left: Point, module_size: u32) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    let Ok(code) = encode_qr_code(data) else {
+        return Ok(());
+    };
+    let width = code.width();
+    let side = (width as u32 + 2 * QR_QUIET_ZONE) * module_size;
+    Rectangle::new(top_left, Size::new(side, side))
+        .into_styled(PrimitiveStyle::with_fill(TriColor::White))
+        .draw(target)?;
+    let origin = top_left + Point::new((QR_QUIET_ZONE * module_size) as i32, (QR_QUIET_ZONE * module_size) as i32);
+    for (idx, color) in code.to_colors().into_iter().enumerate() {
+        if color == qrcode::Color::Dark {
+            let module = Point::new((idx % width) as i32, (idx / width) as i32) * module_size as i32;
+            target.fill_solid(
+                &Rectangle::new(origin + module, Size::new(module_size, module_size)),
+                TriColor::Black,
+            )?;
+        }
+    }
+    Ok(())
+}
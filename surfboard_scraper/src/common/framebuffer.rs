@@ -0,0 +1,152 @@
+use anyhow::{Result, anyhow};
+use embedded_graphics::prelude::*;
+use embedded_graphics_simulator::SimulatorDisplay;
+use epd_waveshare::color::TriColor;
+
+/// Marks a packed framebuffer, the firmware falls back to QOI (`qoif`) for anything else
+pub const EPD_MAGIC: &[u8; 4] = b"epdf";
+
+/// Magic, width and height as little-endian u16, number of planes
+pub const EPD_HEADER_BYTES: usize = 9;
+
+/// Longest run PackBits can encode in one control byte
+const MAX_RUN: usize = 128;
+
+/// Encode a rendered screen in the layout of the epd7in5_v2 `Display7in5` buffer: rows of 1bpp, MSB first.
+///
+/// The firmware draws ink as `Color::White` (bit set), so black and chromatic pixels are set in the first
+/// plane. With `chromatic_plane` a second plane follows in which only the chromatic pixels are set.
+/// The planes are compressed together with PackBits.
+pub fn encode_epd(display: &SimulatorDisplay<TriColor>, chromatic_plane: bool) -> Vec<u8> {
+    let size = display.size();
+    let mut planes = pack_plane(display, |color| color != TriColor::White);
+    if chromatic_plane {
+        planes.extend(pack_plane(display, |color| color == TriColor::Chromatic));
+    }
+
+    let mut bytes = Vec::with_capacity(EPD_HEADER_BYTES + planes.len() / 4);
+    bytes.extend_from_slice(EPD_MAGIC);
+    bytes.extend_from_slice(&(size.width as u16).to_le_bytes());
+    bytes.extend_from_slice(&(size.height as u16).to_le_bytes());
+    bytes.push(if chromatic_plane { 2 } else { 1 });
+    bytes.extend(packbits(&planes));
+    bytes
+}
+
+/// Split an encoded framebuffer into its width, height and decompressed planes
+pub fn decode_epd(bytes: &[u8]) -> Result<(u32, u32, Vec<Vec<u8>>)> {
+    if bytes.len() < EPD_HEADER_BYTES || &bytes[..4] != EPD_MAGIC {
+        return Err(anyhow!("Not a packed framebuffer"));
+    }
+    let width = u16::from_le_bytes([bytes[4], bytes[5]]) as u32;
+    let height = u16::from_le_bytes([bytes[6], bytes[7]]) as u32;
+    let plane_bytes = width.div_ceil(8) as usize * height as usize;
+    let data = unpackbits(&bytes[EPD_HEADER_BYTES..])?;
+    if data.len() != plane_bytes * bytes[8] as usize {
+        return Err(anyhow!("Expected {} planes of {} bytes, got {} bytes", bytes[8], plane_bytes, data.len()));
+    }
+    Ok((width, height, data.chunks(plane_bytes).map(|plane| plane.to_vec()).collect()))
+}
+
+/// A published image back as a display, a packed framebuffer or a QOI with the firmware's reading of its colors
+pub fn decode_display(bytes: &[u8]) -> Result<SimulatorDisplay<TriColor>> {
+    if bytes.starts_with(EPD_MAGIC) {
+        let (width, height, planes) = decode_epd(bytes)?;
+        let row_bytes = width.div_ceil(8) as usize;
+        let mut display = SimulatorDisplay::new(Size::new(width, height));
+        let pixels = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| {
+            let is_set = |plane: &Vec<u8>| plane[y as usize * row_bytes + x as usize / 8] & (0x80 >> (x % 8)) != 0;
+            let color = match (is_set(&planes[0]), planes.get(1).is_some_and(is_set)) {
+                (false, _) => TriColor::White,
+                (true, false) => TriColor::Black,
+                (true, true) => TriColor::Chromatic,
+            };
+            Pixel(Point::new(x as i32, y as i32), color)
+        });
+        // drawing on a simulator display can't fail
+        let _ = display.draw_iter(pixels);
+        return Ok(display);
+    }
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::Qoi)?.to_rgb8();
+    let mut display = SimulatorDisplay::new(Size::new(image.width(), image.height()));
+    let pixels = image.enumerate_pixels().map(|(x, y, pixel)| {
+        let [r, g, b] = pixel.0;
+        let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+        let color = match pixel.0 {
+            [255, 0, 0] => TriColor::Chromatic,
+            _ if luminance <= 128.0 => TriColor::Black,
+            _ => TriColor::White,
+        };
+        Pixel(Point::new(x as i32, y as i32), color)
+    });
+    let _ = display.draw_iter(pixels);
+    Ok(display)
+}
+
+fn pack_plane<F>(display: &SimulatorDisplay<TriColor>, is_set: F) -> Vec<u8>
+where
+    F: Fn(TriColor) -> bool,
+{
+    let size = display.size();
+    let row_bytes = size.width.div_ceil(8) as usize;
+    let mut plane = vec![0u8; row_bytes * size.height as usize];
+    for y in 0..size.height {
+        for x in 0..size.width {
+            if is_set(display.get_pixel(Point::new(x as i32, y as i32))) {
+                plane[y as usize * row_bytes + x as usize / 8] |= 0x80 >> (x % 8);
+            }
+        }
+    }
+    plane
+}
+
+/// PackBits: a control byte `n` below 128 is followed by `n + 1` literal bytes, above 128 by one byte repeated `257 - n` times
+pub fn packbits(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut literal_start = 0;
+    let mut i = 0;
+    while i < data.len() {
+        let run = data[i..].iter().take(MAX_RUN).take_while(|&&b| b == data[i]).count();
+        // runs of two are cheaper as part of a literal
+        if run >= 3 {
+            push_literal(&mut out, &data[literal_start..i]);
+            out.push((257 - run) as u8);
+            out.push(data[i]);
+            i += run;
+            literal_start = i;
+        } else {
+            i += run;
+        }
+    }
+    push_literal(&mut out, &data[literal_start..]);
+    out
+}
+
+fn push_literal(out: &mut Vec<u8>, literal: &[u8]) {
+    for chunk in literal.chunks(MAX_RUN) {
+        out.push((chunk.len() - 1) as u8);
+        out.extend_from_slice(chunk);
+    }
+}
+
+/// Inverse of `packbits`
+pub fn unpackbits(data: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        let control = data[i] as usize;
+        i += 1;
+        if control < 128 {
+            let literal = data
+                .get(i..i + control + 1)
+                .ok_or(anyhow!("Truncated literal at byte {}", i))?;
+            out.extend_from_slice(literal);
+            i += control + 1;
+        } else if control > 128 {
+            let byte = *data.get(i).ok_or(anyhow!("Truncated run at byte {}", i))?;
+            out.extend(std::iter::repeat_n(byte, 257 - control));
+            i += 1;
+        }
+    }
+    Ok(out)
+}
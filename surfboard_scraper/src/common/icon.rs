@@ -0,0 +1,96 @@
+use core::fmt::Write;
+use embedded_graphics::image::ImageRaw;
+use embedded_graphics::pixelcolor::BinaryColor;
+use epd_waveshare::color::TriColor;
+use image::imageops::FilterType;
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use crate::common::bitmap::TriColorBitmap;
+
+/// Icon reduced to ink and paper, in the row-padded MSB-first layout of `ImageRaw<BinaryColor>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconBitmap {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl IconBitmap {
+    /// Convert an icon with dark ink on a light or transparent background.
+    ///
+    /// With `size` the icon is scaled to fit a `size`x`size` square and centered on it, so every icon in a set
+    /// lines up, otherwise it keeps its own size. Pixels darker than `threshold` become ink, or with `dither`
+    /// grey areas are error-diffused instead.
+    pub fn from_image(image: &DynamicImage, size: Option<u32>, threshold: u8, dither: bool) -> Self {
+        let mut rgba = flatten_alpha(&image.to_rgba8());
+        if let Some(size) = size {
+            let scaled = DynamicImage::ImageRgba8(rgba).resize(size, size, FilterType::CatmullRom);
+            rgba = RgbaImage::from_pixel(size, size, Rgba([255, 255, 255, 255]));
+            let (x, y) = ((size - scaled.width()) / 2, (size - scaled.height()) / 2);
+            image::imageops::overlay(&mut rgba, &scaled.to_rgba8(), x as i64, y as i64);
+        }
+        let (width, height) = rgba.dimensions();
+
+        let ink: Vec<bool> = if dither {
+            let rgb = DynamicImage::ImageRgba8(rgba).to_rgb8();
+            let dithered = TriColorBitmap::dithered(&rgb, false, 1.0);
+            (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| dithered.pixel(x, y) == TriColor::Black)
+                .collect()
+        } else {
+            let luma = DynamicImage::ImageRgba8(rgba).to_luma8();
+            luma.pixels().map(|pixel| pixel.0[0] < threshold).collect()
+        };
+
+        let row_bytes = width.div_ceil(8) as usize;
+        let mut data = vec![0u8; row_bytes * height as usize];
+        for (i, _) in ink.iter().enumerate().filter(|(_, is_ink)| **is_ink) {
+            let (x, y) = (i % width as usize, i / width as usize);
+            data[y * row_bytes + x / 8] |= 0x80 >> (x % 8);
+        }
+        IconBitmap { width, height, data }
+    }
+
+    pub fn image_raw(&self) -> ImageRaw<'_, BinaryColor> {
+        ImageRaw::new(&self.data, self.width)
+    }
+
+    /// Rust source for a `pub const` holding this icon, one row of the bitmap per line
+    pub fn to_rust_const(&self, name: &str) -> String {
+        let mut source = String::new();
+        writeln!(source, "#[rustfmt::skip]").unwrap();
+        writeln!(source, "pub const {}: ImageRaw<BinaryColor> = ImageRaw::new(&[", name).unwrap();
+        for row in self.data.chunks(self.width.div_ceil(8) as usize) {
+            let bytes: Vec<String> = row.iter().map(|byte| format!("0b{:08b}", byte)).collect();
+            writeln!(source, "\t{},", bytes.join(", ")).unwrap();
+        }
+        writeln!(source, "], {});", self.width).unwrap();
+        source
+    }
+}
+
+/// Constant name for an icon file, `partly-cloudy.png` becomes `PARTLY_CLOUDY`
+pub fn const_name(file_stem: &str) -> String {
+    file_stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Composite onto white so transparent backgrounds read as paper
+fn flatten_alpha(image: &RgbaImage) -> RgbaImage {
+    let mut flat = image.clone();
+    for pixel in flat.pixels_mut() {
+        let [r, g, b, a] = pixel.0;
+        let blend = |channel: u8| ((channel as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+        *pixel = Rgba([blend(r), blend(g), blend(b), 255]);
+    }
+    flat
+}
@@ -1 +1,4 @@
-pub mod draw_utils;
\ No newline at end of file
+pub mod bitmap;
+pub mod draw_utils;
+pub mod framebuffer;
+pub mod icon;
\ No newline at end of file
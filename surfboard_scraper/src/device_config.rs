@@ -1,15 +1,22 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use chrono::NaiveDate;
 use embedded_graphics::prelude::Size;
 use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay};
 use epd_waveshare::color::TriColor;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::{
+    common::framebuffer::encode_epd,
+    locale::Locale,
     message::data::MessageData,
-    screen::{Screen, ScreenIdentifier},
+    photo::data::PhotoData,
+    publish::PublishConfig,
+    refresh::{RefreshInterval, RefreshPolicy},
+    schedule::ScreenSchedule,
+    screen::{OutputFormat, RenderContext, SERVER_SIDE_IMAGE_BYTES, Screen, ScreenIdentifier},
     surf_report_24h::data::SurfReport24HData,
     surf_report_week::data::SurfReportWeekData,
 };
@@ -18,6 +25,59 @@ use crate::{
 pub struct Configuration {
     pub screens: Vec<ScreenConfiguration>,
     pub screen_saver: Option<ScreenConfiguration>,
+
+    // screen savers that take over from `screen_saver` while their schedule is active, first match wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scheduled_screen_savers: Vec<ScreenConfiguration>,
+
+    // language and date/time conventions of every screen on this device
+    #[serde(default)]
+    pub locale: Locale,
+
+    // where the screens and this config are uploaded, server-side only so devices never see credentials
+    #[serde(default, skip_serializing)]
+    pub publish: Option<PublishConfig>,
+}
+
+impl Configuration {
+    /// Device-facing configuration containing only the screens that are active on `date`
+    pub fn active_on(&self, date: NaiveDate) -> Configuration {
+        let screen_saver = self
+            .scheduled_screen_savers
+            .iter()
+            .chain(self.screen_saver.iter())
+            .find(|screen| screen.schedule.is_active(date))
+            .map(ScreenConfiguration::without_schedule);
+        Configuration {
+            screens: self
+                .screens
+                .iter()
+                .filter(|screen| screen.schedule.is_active(date))
+                .map(ScreenConfiguration::without_schedule)
+                .collect(),
+            screen_saver,
+            scheduled_screen_savers: Vec::new(),
+            locale: self.locale,
+            publish: self.publish.clone(),
+        }
+    }
+
+    /// Every screen whatever its schedule, screen savers last. On an `active_on` configuration that's the screens
+    /// and the screen saver of the day
+    pub fn all_screens(&self) -> impl Iterator<Item = &ScreenConfiguration> {
+        self.screens
+            .iter()
+            .chain(self.screen_saver.iter())
+            .chain(self.scheduled_screen_savers.iter())
+    }
+
+    /// Settings shared by all screens of this device
+    pub fn render_context(&self) -> RenderContext {
+        RenderContext {
+            locale: self.locale,
+            ..RenderContext::default()
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -26,48 +86,142 @@ pub struct ScreenConfiguration {
     pub key: ScreenIdentifier,
     pub url: String,
     pub params: HashMap<String, Value>,
+    #[serde(default, skip_serializing_if = "OutputFormat::is_qoi")]
+    pub format: OutputFormat,
+    #[serde(flatten)]
+    pub schedule: ScreenSchedule,
+    // how long the screen may fail to render before its last good image is republished as stale, see `stale.rs`
+    #[serde(default = "default_stale_after_hours", skip_serializing)]
+    pub stale_after_hours: u32,
+    // `content_hash` of the published image, set in the device-facing config so devices can skip unchanged screens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    // when the screen renders again, server-side only, see `refresh.rs`
+    #[serde(default, skip_serializing)]
+    pub refresh: Option<RefreshPolicy>,
+}
+
+/// Two missed 3-hourly updates
+fn default_stale_after_hours() -> u32 {
+    6
 }
 
 impl ScreenConfiguration {
-    pub async fn draw_to_qoi<W>(&self, writer: &mut W) -> Result<()>
+    fn without_schedule(&self) -> ScreenConfiguration {
+        ScreenConfiguration {
+            schedule: ScreenSchedule::default(),
+            ..self.clone()
+        }
+    }
+
+    /// The screen's `"refresh"`, by default messages and single photos on change and everything else every 3
+    /// hours, a photo directory picks a new photo each time
+    pub fn refresh_policy(&self) -> RefreshPolicy {
+        let photo_file = || {
+            self.params
+                .get("path")
+                .and_then(|path| path.as_str())
+                .is_some_and(|path| !Path::new(path).is_dir())
+        };
+        self.refresh.clone().unwrap_or(match self.key {
+            ScreenIdentifier::Message => RefreshPolicy::OnChange,
+            ScreenIdentifier::Photo if photo_file() => RefreshPolicy::OnChange,
+            ScreenIdentifier::SurfReport24h | ScreenIdentifier::SurfReportWeek | ScreenIdentifier::Photo => {
+                RefreshPolicy::Interval(RefreshInterval::default())
+            }
+        })
+    }
+
+    /// Render the screen in its configured output format
+    pub async fn render<W>(&self, context: &RenderContext, writer: &mut W) -> Result<()>
+    where
+        W: std::io::Write + std::io::Seek,
+    {
+        let bytes = self.encode(&self.draw_to_display(context).await?)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Encode a drawn screen in its configured output format, checking that it fits the device's buffer
+    pub fn encode(&self, display: &SimulatorDisplay<TriColor>) -> Result<Vec<u8>> {
+        let bytes = match self.format {
+            OutputFormat::Qoi => {
+                let output_settings = OutputSettingsBuilder::new().scale(1).build();
+                let output_image = display.to_rgb_output_image(&output_settings);
+                let mut bytes: Vec<u8> = Vec::new();
+                output_image
+                    .as_image_buffer()
+                    .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Qoi)?;
+                bytes
+            }
+            OutputFormat::Epd1 => encode_epd(display, false),
+            OutputFormat::Epd2 => encode_epd(display, true),
+        };
+        if bytes.len() > SERVER_SIDE_IMAGE_BYTES {
+            return Err(anyhow!(
+                "Screen {} is {} bytes as {:?}, the device buffer holds {}",
+                self.id,
+                bytes.len(),
+                self.format,
+                SERVER_SIDE_IMAGE_BYTES
+            ));
+        }
+        Ok(bytes)
+    }
+
+    pub async fn draw_to_qoi<W>(&self, context: &RenderContext, writer: &mut W) -> Result<()>
     where
         W: std::io::Write + std::io::Seek,
     {
         match self.key {
             ScreenIdentifier::SurfReport24h => {
                 let params = SurfReport24HData::parse_params(&self.params)?;
-                SurfReport24HData::from_params(&params).await?.draw_to_qoi(writer)?;
+                SurfReport24HData::from_params(&params, context).await?.draw_to_qoi(writer)?;
                 Ok(())
             }
             ScreenIdentifier::SurfReportWeek => {
                 let params = SurfReportWeekData::parse_params(&self.params)?;
-                SurfReportWeekData::from_params(&params).await?.draw_to_qoi(writer)?;
+                SurfReportWeekData::from_params(&params, context).await?.draw_to_qoi(writer)?;
                 Ok(())
             }
             ScreenIdentifier::Message => {
                 let params = MessageData::parse_params(&self.params)?;
-                MessageData::from_params(&params).await?.draw_to_qoi(writer)?;
+                MessageData::from_params(&params, context).await?.draw_to_qoi(writer)?;
+                Ok(())
+            }
+            ScreenIdentifier::Photo => {
+                let params = PhotoData::parse_params(&self.params)?;
+                PhotoData::from_params(&params, context).await?.draw_to_qoi(writer)?;
                 Ok(())
             }
         }
     }
 
-    pub async fn draw_to_png(&self, png_path: &str) -> Result<()> {
+    pub async fn draw_to_display(&self, context: &RenderContext) -> Result<SimulatorDisplay<TriColor>> {
         let mut display = SimulatorDisplay::<TriColor>::new(Size::new(800, 480));
         match self.key {
             ScreenIdentifier::SurfReport24h => {
                 let params = SurfReport24HData::parse_params(&self.params)?;
-                SurfReport24HData::from_params(&params).await?.draw(&mut display)?;
+                SurfReport24HData::from_params(&params, context).await?.draw(&mut display)?;
             }
             ScreenIdentifier::SurfReportWeek => {
                 let params = SurfReportWeekData::parse_params(&self.params)?;
-                SurfReportWeekData::from_params(&params).await?.draw(&mut display)?;
+                SurfReportWeekData::from_params(&params, context).await?.draw(&mut display)?;
             }
             ScreenIdentifier::Message => {
                 let params = MessageData::parse_params(&self.params)?;
-                MessageData::from_params(&params).await?.draw(&mut display)?;
+                MessageData::from_params(&params, context).await?.draw(&mut display)?;
+            }
+            ScreenIdentifier::Photo => {
+                let params = PhotoData::parse_params(&self.params)?;
+                PhotoData::from_params(&params, context).await?.draw(&mut display)?;
             }
         }
+        Ok(display)
+    }
+
+    pub async fn draw_to_png(&self, context: &RenderContext, png_path: &str) -> Result<()> {
+        let display = self.draw_to_display(context).await?;
         let output_settings = OutputSettingsBuilder::new().scale(1).build();
         let output_image = display.to_grayscale_output_image(&output_settings);
         output_image.save_png(png_path).expect("Failed to save test image");
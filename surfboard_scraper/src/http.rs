@@ -1,8 +1,54 @@
-use anyhow::{Result, anyhow};
-use reqwest::Error;
+use anyhow::{Context, Result, anyhow};
+use reqwest::{Error, Url};
 use serde::de::DeserializeOwned;
+use std::{path::PathBuf, time::Instant};
+use tracing::debug;
 
-pub async fn fetch<T: DeserializeOwned>(url: &str) -> Result<T> {
+use crate::metrics::METRICS;
+
+/// Where screens get their Surfline data from
+#[derive(Debug, Clone, Default)]
+pub enum DataSource {
+    #[default]
+    Live,
+    /// Saved API responses, see `fixture_path` for the layout
+    Fixtures(PathBuf),
+}
+
+/// Fetch and parse a Surfline API response, timed per endpoint in `METRICS`. Failures are left to the caller to
+/// log with what the response was for
+pub async fn fetch<T: DeserializeOwned>(source: &DataSource, url: &str) -> Result<T> {
+    let started = Instant::now();
+    let result = fetch_from(source, url).await;
+    let parsed = Url::parse(url).ok();
+    let endpoint = parsed.as_ref().and_then(endpoint);
+    let endpoint = endpoint.as_deref().unwrap_or("unknown");
+    let spot_id = parsed.as_ref().and_then(|url| query_value(url, "spotId"));
+    METRICS.fetched(endpoint, started.elapsed(), result.is_ok());
+    debug!(
+        endpoint,
+        spot_id = spot_id.as_deref(),
+        duration_ms = started.elapsed().as_millis() as u64,
+        succeeded = result.is_ok(),
+        "Fetched"
+    );
+    result
+}
+
+async fn fetch_from<T: DeserializeOwned>(source: &DataSource, url: &str) -> Result<T> {
+    match source {
+        DataSource::Live => fetch_live(url).await,
+        DataSource::Fixtures(dir) => {
+            let path = dir.join(fixture_path(url)?);
+            let contents = tokio::fs::read_to_string(&path)
+                .await
+                .with_context(|| format!("Missing fixture {:?} for {}", path, url))?;
+            Ok(serde_json::from_str(&contents).with_context(|| format!("Invalid fixture {:?}", path))?)
+        }
+    }
+}
+
+async fn fetch_live<T: DeserializeOwned>(url: &str) -> Result<T> {
     let response = reqwest::get(url).await;
 
     match response {
@@ -19,3 +65,28 @@ pub async fn fetch<T: DeserializeOwned>(url: &str) -> Result<T> {
         Err(err) => Err(err.into()),
     }
 }
+
+/// Fixture file for a Surfline API URL relative to the fixture directory: `<spot id>/<endpoint>.json`, with
+/// `_<days>d` appended to the endpoint for forecasts, e.g. `5842041f4e65fad6a7708807/wave_7d.json`
+pub fn fixture_path(url: &str) -> Result<PathBuf> {
+    let url = Url::parse(url)?;
+    let endpoint = endpoint(&url).ok_or(anyhow!("No endpoint in {}", url))?;
+    let spot_id = query_value(&url, "spotId").ok_or(anyhow!("No spotId in {}", url))?;
+    let file_name = match query_value(&url, "days") {
+        Some(days) => format!("{}_{}d.json", endpoint, days),
+        None => format!("{}.json", endpoint),
+    };
+    Ok(PathBuf::from(spot_id).join(file_name))
+}
+
+/// Last path segment of a Surfline API URL, e.g. `wave`
+fn endpoint(url: &Url) -> Option<String> {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|endpoint| !endpoint.is_empty())
+        .map(|endpoint| endpoint.to_string())
+}
+
+fn query_value(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs().find(|(k, _)| k == key).map(|(_, v)| v.into_owned())
+}
@@ -1,42 +1,97 @@
-// Written by ./tools/icon_processing.ipynb
+// Written by `icongen tools/icons --output src/image_data.rs` (src/bin/icongen.rs), edit the icons and rerun instead of editing this file
+
+use embedded_graphics::image::ImageRaw;
+use embedded_graphics::pixelcolor::BinaryColor;
 
 #[rustfmt::skip]
-pub const WEATHER_SUNNY: &[u8] = &[
-	0b00000000, 0b00000001, 0b10000000, 0b00000000,
+pub const CLOUDY: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000011, 0b11111000, 0b00000000,
+	0b00000000, 0b00001111, 0b11111110, 0b00000000,
+	0b00000000, 0b00011100, 0b00001111, 0b00000000,
+	0b00000000, 0b00111000, 0b00000011, 0b10000000,
+	0b00000001, 0b11110000, 0b00000001, 0b10000000,
+	0b00000111, 0b11100000, 0b00000000, 0b11000000,
+	0b00001110, 0b00000000, 0b00000000, 0b11000000,
+	0b00011100, 0b00000000, 0b00000000, 0b11000000,
+	0b00011000, 0b00000000, 0b00000000, 0b11100000,
+	0b00111000, 0b00000000, 0b00000000, 0b11111000,
+	0b01110000, 0b00000000, 0b00000000, 0b00011100,
+	0b01100000, 0b00000000, 0b00000000, 0b00001100,
+	0b11000000, 0b00000000, 0b00000000, 0b00001100,
+	0b11000000, 0b00000000, 0b00000000, 0b00001100,
+	0b11000000, 0b00000000, 0b00000000, 0b00001100,
+	0b11100000, 0b00000000, 0b00000000, 0b00001100,
+	0b01100000, 0b00000000, 0b00000000, 0b00011100,
+	0b01110000, 0b00000000, 0b00000000, 0b00111000,
+	0b00111111, 0b11111111, 0b11111111, 0b11110000,
+	0b00001111, 0b11111111, 0b11111111, 0b11000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const HAZE: ImageRaw<BinaryColor> = ImageRaw::new(&[
 	0b00000000, 0b00000001, 0b10000000, 0b00000000,
-	0b00000000, 0b11000001, 0b10000011, 0b10000000,
-	0b00000000, 0b11000001, 0b10000011, 0b00000000,
-	0b00000000, 0b01100001, 0b10000110, 0b00000000,
-	0b00000000, 0b00110001, 0b10000110, 0b00000000,
-	0b00000000, 0b00110000, 0b00001100, 0b00000000,
-	0b00110000, 0b00000111, 0b11100000, 0b00001100,
-	0b00111100, 0b00011111, 0b11111000, 0b00011100,
-	0b00001111, 0b00111111, 0b11111100, 0b01111000,
-	0b00000010, 0b01111111, 0b11111110, 0b01100000,
-	0b00000000, 0b11111111, 0b11111111, 0b01000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000011, 0b00000000, 0b00000000, 0b11000000,
+	0b00000011, 0b10000000, 0b00000001, 0b11000000,
+	0b00000001, 0b00000000, 0b00000000, 0b10000000,
+	0b00000000, 0b00000011, 0b11000000, 0b00000000,
+	0b00000000, 0b00001111, 0b11110000, 0b00000000,
+	0b00000000, 0b00011111, 0b11111000, 0b00000000,
+	0b00000000, 0b00111111, 0b11111100, 0b00000000,
+	0b00000000, 0b01111111, 0b11111110, 0b00000000,
+	0b00000000, 0b01111111, 0b11111110, 0b00000000,
+	0b00111000, 0b01111111, 0b11111110, 0b00011100,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00111111, 0b11111111, 0b11111111, 0b11111100,
+	0b00111111, 0b11111111, 0b11111111, 0b11111100,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000111, 0b11111111, 0b11111111, 0b11100000,
+	0b00000111, 0b11111111, 0b11111111, 0b11100000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b11111111, 0b11111111, 0b00000000,
-	0b00000001, 0b11111111, 0b11111111, 0b10000000,
-	0b00000001, 0b11111111, 0b11111111, 0b10000000,
-	0b11111001, 0b11111111, 0b11111111, 0b10011111,
-	0b00000001, 0b11111111, 0b11111111, 0b10011111,
-	0b00000001, 0b11111111, 0b11111111, 0b10000000,
-	0b00000001, 0b11111111, 0b11111111, 0b10000000,
 	0b00000000, 0b11111111, 0b11111111, 0b00000000,
-	0b00000110, 0b11111111, 0b11111111, 0b01100000,
-	0b00011110, 0b01111111, 0b11111110, 0b01110000,
-	0b00111000, 0b00111111, 0b11111100, 0b00111100,
-	0b00110000, 0b00011111, 0b11111000, 0b00001100,
-	0b00000000, 0b00000111, 0b11100000, 0b00000000,
-	0b00000000, 0b01110000, 0b00001110, 0b00000000,
-	0b00000000, 0b01100001, 0b10000110, 0b00000000,
-	0b00000000, 0b11100001, 0b10000011, 0b00000000,
-	0b00000000, 0b11000001, 0b10000011, 0b10000000,
-	0b00000001, 0b10000001, 0b10000001, 0b10000000,
-	0b00000000, 0b00000001, 0b10000000, 0b00000000,
-	0b00000000, 0b00000001, 0b10000000, 0b00000000,
-];
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
 #[rustfmt::skip]
-pub const MOSTLY_CLEAR: &[u8] = &[
+pub const MIST: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00001100, 0b00000000, 0b00000000,
+	0b00000000, 0b01111111, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000001, 0b11111111, 0b11000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000001, 0b11111111, 0b11111100, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00001111, 0b11111111, 0b11111111, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00011111, 0b11111111, 0b11111111, 0b10000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b11111111, 0b11111111, 0b11111111, 0b10000000,
+	0b11111111, 0b11111111, 0b11111111, 0b00000000,
+	0b11111111, 0b11111111, 0b11111110, 0b00000000,
+	0b01111111, 0b11111111, 0b11111100, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const MOSTLY_CLEAR: ImageRaw<BinaryColor> = ImageRaw::new(&[
 	0b00000000, 0b00000010, 0b00000000, 0b00000000,
 	0b00000000, 0b00000010, 0b00000000, 0b00000000,
 	0b00000001, 0b10000010, 0b00001100, 0b00000000,
@@ -69,9 +124,169 @@ pub const MOSTLY_CLEAR: &[u8] = &[
 	0b00000000, 0b00000000, 0b00001100, 0b00000001,
 	0b00000000, 0b00000000, 0b00010000, 0b00000001,
 	0b00000000, 0b00000000, 0b00011111, 0b11111110,
-];
+], 32);
+
 #[rustfmt::skip]
-pub const PARTLY_CLOUDY: &[u8] = &[
+pub const MOSTLY_CLOUDY: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00001000, 0b10001000, 0b00000000, 0b00000000,
+	0b00001100, 0b10001000, 0b00000000, 0b00000000,
+	0b00000100, 0b10011100, 0b00000000, 0b00000000,
+	0b01000000, 0b11111111, 0b00000000, 0b00000000,
+	0b01110111, 0b11000001, 0b10000000, 0b00000000,
+	0b00010111, 0b10000000, 0b11000000, 0b00000000,
+	0b00001111, 0b00000000, 0b01100000, 0b00000000,
+	0b00001111, 0b00000000, 0b01111100, 0b00000000,
+	0b11101111, 0b00000000, 0b00100111, 0b00000000,
+	0b00001111, 0b00000000, 0b00000011, 0b00000000,
+	0b00011000, 0b00000000, 0b00000001, 0b10000000,
+	0b01111000, 0b00000000, 0b00000000, 0b10000000,
+	0b11111000, 0b00000000, 0b00000000, 0b10000000,
+	0b11000000, 0b00000000, 0b00000001, 0b10000000,
+	0b10000000, 0b00000000, 0b00000001, 0b00000000,
+	0b11000000, 0b00000000, 0b00000111, 0b00000000,
+	0b01111111, 0b11111111, 0b11111110, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const NIGHT_CLEAR: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00011000, 0b00000000, 0b00000000,
+	0b00000000, 0b11110000, 0b00000000, 0b00000000,
+	0b00000001, 0b11110000, 0b00000000, 0b00000000,
+	0b00000011, 0b11100000, 0b00000000, 0b00000000,
+	0b00000111, 0b11100000, 0b00000000, 0b00000000,
+	0b00001111, 0b11000000, 0b00000000, 0b00000000,
+	0b00011111, 0b11000000, 0b00000000, 0b00000000,
+	0b00011111, 0b11000000, 0b00000000, 0b00000000,
+	0b00011111, 0b11000000, 0b00000000, 0b00000000,
+	0b00111111, 0b11100000, 0b00000000, 0b00000000,
+	0b00111111, 0b11100000, 0b00000000, 0b00000000,
+	0b00111111, 0b11100000, 0b00000000, 0b00000000,
+	0b00111111, 0b11110000, 0b00000000, 0b00000000,
+	0b00111111, 0b11111000, 0b00000000, 0b00000000,
+	0b00111111, 0b11111100, 0b00000000, 0b00000000,
+	0b00011111, 0b11111110, 0b00000000, 0b00000000,
+	0b00011111, 0b11111111, 0b00000000, 0b00000000,
+	0b00011111, 0b11111111, 0b11111111, 0b10000000,
+	0b00001111, 0b11111111, 0b11111111, 0b00000000,
+	0b00000111, 0b11111111, 0b11111110, 0b00000000,
+	0b00000011, 0b11111111, 0b11111100, 0b00000000,
+	0b00000001, 0b11111111, 0b11111000, 0b00000000,
+	0b00000000, 0b11111111, 0b11110000, 0b00000000,
+	0b00000000, 0b00011111, 0b10000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const NIGHT_MOSTLY_CLEAR: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b11000000, 0b00000000, 0b00000000,
+	0b00000011, 0b10000000, 0b00000000, 0b00000000,
+	0b00001111, 0b10000000, 0b00000000, 0b00000000,
+	0b00011111, 0b00000000, 0b00000000, 0b00000000,
+	0b00011111, 0b00000000, 0b00000000, 0b00000000,
+	0b00111111, 0b00000000, 0b00000000, 0b00000000,
+	0b00111111, 0b00000000, 0b00000000, 0b00000000,
+	0b01111111, 0b00000000, 0b00000000, 0b00000000,
+	0b01111111, 0b00000000, 0b00000000, 0b00000000,
+	0b01111111, 0b10000000, 0b00000000, 0b00000000,
+	0b01111111, 0b10000000, 0b00000000, 0b00000000,
+	0b01111111, 0b11000000, 0b00000000, 0b00000000,
+	0b01111111, 0b11100000, 0b00111111, 0b10000000,
+	0b00111111, 0b11110000, 0b01111111, 0b11000000,
+	0b00111111, 0b11111111, 0b11100000, 0b11100000,
+	0b00011111, 0b11111111, 0b11000000, 0b01100000,
+	0b00011111, 0b11111110, 0b00000000, 0b01110000,
+	0b00001111, 0b11111100, 0b00000000, 0b00110000,
+	0b00000011, 0b11111000, 0b00000000, 0b00111100,
+	0b00000000, 0b11110000, 0b00000000, 0b00001100,
+	0b00000000, 0b01100000, 0b00000000, 0b00001100,
+	0b00000000, 0b01100000, 0b00000000, 0b00000110,
+	0b00000000, 0b01100000, 0b00000000, 0b00001100,
+	0b00000000, 0b00110000, 0b00000000, 0b00001100,
+	0b00000000, 0b00111111, 0b11111111, 0b11111100,
+	0b00000000, 0b00011111, 0b11111111, 0b11110000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const NIGHT_PARTLY_CLOUDY: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000001, 0b00000000, 0b00000000, 0b00000000,
+	0b00000111, 0b00000000, 0b00000000, 0b00000000,
+	0b00001110, 0b00000000, 0b00100000, 0b00000000,
+	0b00011110, 0b00000001, 0b11111100, 0b00000000,
+	0b00111110, 0b00000111, 0b11111111, 0b00000000,
+	0b00111110, 0b00001110, 0b00000011, 0b10000000,
+	0b00111110, 0b00011100, 0b00000001, 0b11000000,
+	0b00111111, 0b11111000, 0b00000000, 0b11000000,
+	0b00111111, 0b11110000, 0b00000000, 0b11100000,
+	0b00111111, 0b00000000, 0b00000000, 0b01100000,
+	0b00011110, 0b00000000, 0b00000000, 0b01100000,
+	0b00001110, 0b00000000, 0b00000000, 0b01111000,
+	0b00011100, 0b00000000, 0b00000000, 0b00011100,
+	0b00011000, 0b00000000, 0b00000000, 0b00001100,
+	0b00110000, 0b00000000, 0b00000000, 0b00000110,
+	0b00110000, 0b00000000, 0b00000000, 0b00000110,
+	0b00110000, 0b00000000, 0b00000000, 0b00000110,
+	0b00110000, 0b00000000, 0b00000000, 0b00000110,
+	0b00011000, 0b00000000, 0b00000000, 0b00001100,
+	0b00011100, 0b00000000, 0b00000000, 0b00011100,
+	0b00001111, 0b11111111, 0b11111111, 0b11111000,
+	0b00000011, 0b11111111, 0b11111111, 0b11100000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const NIGHT_SHOWERS: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000001, 0b11111000, 0b00000000,
+	0b00000010, 0b00000111, 0b11111100, 0b00000000,
+	0b00001110, 0b00001111, 0b00001110, 0b00000000,
+	0b00011100, 0b00011100, 0b00000011, 0b00000000,
+	0b00011100, 0b11111000, 0b00000001, 0b10000000,
+	0b00111101, 0b11110000, 0b00000001, 0b10000000,
+	0b00111111, 0b10000000, 0b00000001, 0b10000000,
+	0b00111111, 0b00000000, 0b00000001, 0b11100000,
+	0b00111111, 0b00000000, 0b00000000, 0b11110000,
+	0b00011100, 0b00000000, 0b00000000, 0b00110000,
+	0b00011100, 0b00000000, 0b00000000, 0b00111000,
+	0b00011100, 0b00000000, 0b00000000, 0b00011000,
+	0b00001100, 0b00000000, 0b00000000, 0b00011000,
+	0b00001100, 0b00000000, 0b00000000, 0b00110000,
+	0b00001110, 0b00000000, 0b00000000, 0b01110000,
+	0b00000111, 0b11111111, 0b11111111, 0b11100000,
+	0b00000011, 0b11111111, 0b11111111, 0b11000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b01100011, 0b00011000, 0b11000000,
+	0b00000000, 0b01000010, 0b00010000, 0b10000000,
+	0b00000000, 0b11000110, 0b00110001, 0b10000000,
+	0b00000000, 0b10000100, 0b00100001, 0b00000000,
+	0b00000001, 0b10001100, 0b01100011, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const PARTLY_CLOUDY: ImageRaw<BinaryColor> = ImageRaw::new(&[
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
@@ -97,37 +312,39 @@ pub const PARTLY_CLOUDY: &[u8] = &[
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
+], 32);
+
 #[rustfmt::skip]
-pub const MOSTLY_CLOUDY: &[u8] = &[
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00001000, 0b10001000, 0b00000000, 0b00000000,
-	0b00001100, 0b10001000, 0b00000000, 0b00000000,
-	0b00000100, 0b10011100, 0b00000000, 0b00000000,
-	0b01000000, 0b11111111, 0b00000000, 0b00000000,
-	0b01110111, 0b11000001, 0b10000000, 0b00000000,
-	0b00010111, 0b10000000, 0b11000000, 0b00000000,
-	0b00001111, 0b00000000, 0b01100000, 0b00000000,
-	0b00001111, 0b00000000, 0b01111100, 0b00000000,
-	0b11101111, 0b00000000, 0b00100111, 0b00000000,
-	0b00001111, 0b00000000, 0b00000011, 0b00000000,
-	0b00011000, 0b00000000, 0b00000001, 0b10000000,
-	0b01111000, 0b00000000, 0b00000000, 0b10000000,
-	0b11111000, 0b00000000, 0b00000000, 0b10000000,
-	0b11000000, 0b00000000, 0b00000001, 0b10000000,
-	0b10000000, 0b00000000, 0b00000001, 0b00000000,
-	0b11000000, 0b00000000, 0b00000111, 0b00000000,
-	0b01111111, 0b11111111, 0b11111110, 0b00000000,
+pub const RAIN: ImageRaw<BinaryColor> = ImageRaw::new(&[
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00111111, 0b00000000, 0b00000000,
+	0b00000000, 0b11111111, 0b10000000, 0b00000000,
+	0b00000000, 0b11111111, 0b11000000, 0b00000000,
+	0b00000001, 0b11111111, 0b11000000, 0b00000000,
+	0b00000001, 0b11111111, 0b11111000, 0b00000000,
+	0b00000001, 0b11111111, 0b11111110, 0b00000000,
+	0b00000111, 0b11111111, 0b11111111, 0b00000000,
+	0b00001111, 0b11111111, 0b11111111, 0b10000000,
+	0b00011111, 0b11111111, 0b11111111, 0b10000000,
+	0b01111111, 0b11111111, 0b11111111, 0b10000000,
+	0b11111111, 0b11111111, 0b11111111, 0b10000000,
+	0b11111111, 0b11111111, 0b11111111, 0b00000000,
+	0b11111111, 0b11111111, 0b11111111, 0b00000000,
+	0b01111111, 0b11111111, 0b11111100, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b10010100, 0b10100000, 0b00000000,
+	0b00000100, 0b10100100, 0b00100000, 0b00000000,
+	0b00000101, 0b00100001, 0b00100000, 0b00000000,
+	0b00001001, 0b00101001, 0b01000000, 0b00000000,
+	0b00001001, 0b01001010, 0b01000000, 0b00000000,
+	0b00001010, 0b01010010, 0b01000000, 0b00000000,
+	0b00010010, 0b00010010, 0b10000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
+], 32);
+
 #[rustfmt::skip]
-pub const SCATTERED_SHOWERS: &[u8] = &[
+pub const SCATTERED_SHOWERS: ImageRaw<BinaryColor> = ImageRaw::new(&[
 	0b00000000, 0b00000000, 0b00100000, 0b00000000,
 	0b00000000, 0b00000001, 0b00100100, 0b00000000,
 	0b00000000, 0b00000000, 0b10001000, 0b00000000,
@@ -153,9 +370,10 @@ pub const SCATTERED_SHOWERS: &[u8] = &[
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00100100, 0b10010000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
+], 32);
+
 #[rustfmt::skip]
-pub const SHOWERS: &[u8] = &[
+pub const SHOWERS: ImageRaw<BinaryColor> = ImageRaw::new(&[
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00111110, 0b00000000, 0b00000000,
 	0b00000000, 0b01110111, 0b10000000, 0b00000000,
@@ -181,65 +399,133 @@ pub const SHOWERS: &[u8] = &[
 	0b00010000, 0b10001000, 0b10000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
+], 32);
+
 #[rustfmt::skip]
-pub const MIST: &[u8] = &[
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00001100, 0b00000000, 0b00000000,
-	0b00000000, 0b01111111, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000001, 0b11111111, 0b11000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000001, 0b11111111, 0b11111100, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00001111, 0b11111111, 0b11111111, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00011111, 0b11111111, 0b11111111, 0b10000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b11111111, 0b11111111, 0b11111111, 0b10000000,
-	0b11111111, 0b11111111, 0b11111111, 0b00000000,
-	0b11111111, 0b11111111, 0b11111110, 0b00000000,
-	0b01111111, 0b11111111, 0b11111100, 0b00000000,
+pub const SNOW: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000111, 0b11111100, 0b00000000,
+	0b00000000, 0b00001111, 0b11111110, 0b00000000,
+	0b00000000, 0b00011100, 0b00000111, 0b00000000,
+	0b00000000, 0b00111000, 0b00000011, 0b10000000,
+	0b00000001, 0b11110000, 0b00000001, 0b10000000,
+	0b00000111, 0b11100000, 0b00000001, 0b11000000,
+	0b00001110, 0b00000000, 0b00000000, 0b11000000,
+	0b00001100, 0b00000000, 0b00000000, 0b11000000,
+	0b00011100, 0b00000000, 0b00000000, 0b11110000,
+	0b00111000, 0b00000000, 0b00000000, 0b00111000,
+	0b00110000, 0b00000000, 0b00000000, 0b00011000,
+	0b01100000, 0b00000000, 0b00000000, 0b00001100,
+	0b01100000, 0b00000000, 0b00000000, 0b00001100,
+	0b01100000, 0b00000000, 0b00000000, 0b00001100,
+	0b01100000, 0b00000000, 0b00000000, 0b00001100,
+	0b00110000, 0b00000000, 0b00000000, 0b00011000,
+	0b00111000, 0b00000000, 0b00000000, 0b00111000,
+	0b00011111, 0b11111111, 0b11111111, 0b11110000,
+	0b00000111, 0b11111111, 0b11111111, 0b11000000,
+	0b00000000, 0b10000000, 0b00000000, 0b10000000,
+	0b00000000, 0b10000000, 0b10000000, 0b10000000,
+	0b00000011, 0b11100000, 0b10000011, 0b11100000,
+	0b00000000, 0b10000011, 0b11100000, 0b10000000,
+	0b00000000, 0b10000000, 0b10000000, 0b10000000,
+	0b00000000, 0b00000000, 0b10000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const THUNDERSTORM: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00000011, 0b11111000, 0b00000000,
+	0b00000000, 0b00001111, 0b11111110, 0b00000000,
+	0b00000000, 0b00011100, 0b00000111, 0b00000000,
+	0b00000000, 0b00111000, 0b00000011, 0b10000000,
+	0b00000001, 0b11110000, 0b00000001, 0b10000000,
+	0b00000111, 0b11100000, 0b00000001, 0b11000000,
+	0b00001110, 0b00000000, 0b00000000, 0b11000000,
+	0b00001100, 0b00000000, 0b00000000, 0b11000000,
+	0b00011100, 0b00000000, 0b00000000, 0b11110000,
+	0b00111000, 0b00000000, 0b00000000, 0b00111000,
+	0b00110000, 0b00000000, 0b00000000, 0b00011000,
+	0b01100000, 0b00000000, 0b01110000, 0b00001100,
+	0b01100000, 0b00000000, 0b11100000, 0b00001100,
+	0b01100000, 0b00000001, 0b11000000, 0b00001100,
+	0b01100000, 0b00000011, 0b10000000, 0b00001100,
+	0b00110000, 0b00000111, 0b11111000, 0b00011000,
+	0b00111000, 0b00001111, 0b11110000, 0b00111000,
+	0b00011111, 0b11111111, 0b11111111, 0b11110000,
+	0b00000111, 0b11111111, 0b11111111, 0b11000000,
+	0b00000000, 0b00000001, 0b10000000, 0b00000000,
+	0b00000000, 0b00000011, 0b00000000, 0b00000000,
+	0b00000000, 0b00000110, 0b00000000, 0b00000000,
+	0b00000000, 0b00000100, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+], 32);
+
+#[rustfmt::skip]
+pub const WAVE: ImageRaw<BinaryColor> = ImageRaw::new(&[
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b00000000, 0b00001111, 0b00000000, 0b00000000,
+	0b00000000, 0b00111111, 0b10000000, 0b00000000,
+	0b00000000, 0b01111111, 0b11000000, 0b00000000,
+	0b00000000, 0b01111111, 0b11100000, 0b00000000,
+	0b00000000, 0b11111110, 0b00000000, 0b00000000,
+	0b00000000, 0b11111110, 0b00000000, 0b00000000,
+	0b00000001, 0b11111110, 0b00000000, 0b00000000,
+	0b00000001, 0b11111100, 0b00000000, 0b00000000,
+	0b00000001, 0b11111100, 0b00000000, 0b00000000,
+	0b00000011, 0b11111100, 0b00000000, 0b00000000,
+	0b00000011, 0b11111110, 0b00000000, 0b00000000,
+	0b00000111, 0b11111110, 0b00000000, 0b00000000,
+	0b00000111, 0b11111111, 0b00000000, 0b00000000,
+	0b00001111, 0b11111111, 0b10000000, 0b00000000,
+	0b00011111, 0b11111111, 0b11000000, 0b00000000,
+	0b00111111, 0b10011111, 0b11011111, 0b00000000,
+	0b00011110, 0b00001111, 0b00000111, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
+	0b01000000, 0b11100000, 0b01110000, 0b00000000,
+	0b01110011, 0b11111001, 0b11111100, 0b00000000,
+	0b00011110, 0b00001111, 0b00000111, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
+], 32);
+
 #[rustfmt::skip]
-pub const RAIN: &[u8] = &[
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00111111, 0b00000000, 0b00000000,
-	0b00000000, 0b11111111, 0b10000000, 0b00000000,
-	0b00000000, 0b11111111, 0b11000000, 0b00000000,
-	0b00000001, 0b11111111, 0b11000000, 0b00000000,
-	0b00000001, 0b11111111, 0b11111000, 0b00000000,
-	0b00000001, 0b11111111, 0b11111110, 0b00000000,
-	0b00000111, 0b11111111, 0b11111111, 0b00000000,
-	0b00001111, 0b11111111, 0b11111111, 0b10000000,
-	0b00011111, 0b11111111, 0b11111111, 0b10000000,
-	0b01111111, 0b11111111, 0b11111111, 0b10000000,
-	0b11111111, 0b11111111, 0b11111111, 0b10000000,
-	0b11111111, 0b11111111, 0b11111111, 0b00000000,
-	0b11111111, 0b11111111, 0b11111111, 0b00000000,
-	0b01111111, 0b11111111, 0b11111100, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b10010100, 0b10100000, 0b00000000,
-	0b00000100, 0b10100100, 0b00100000, 0b00000000,
-	0b00000101, 0b00100001, 0b00100000, 0b00000000,
-	0b00001001, 0b00101001, 0b01000000, 0b00000000,
-	0b00001001, 0b01001010, 0b01000000, 0b00000000,
-	0b00001010, 0b01010010, 0b01000000, 0b00000000,
-	0b00010010, 0b00010010, 0b10000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
+pub const WEATHER_SUNNY: ImageRaw<BinaryColor> = ImageRaw::new(&[
+	0b00000000, 0b00000001, 0b10000000, 0b00000000,
+	0b00000000, 0b00000001, 0b10000000, 0b00000000,
+	0b00000000, 0b11000001, 0b10000011, 0b10000000,
+	0b00000000, 0b11000001, 0b10000011, 0b00000000,
+	0b00000000, 0b01100001, 0b10000110, 0b00000000,
+	0b00000000, 0b00110001, 0b10000110, 0b00000000,
+	0b00000000, 0b00110000, 0b00001100, 0b00000000,
+	0b00110000, 0b00000111, 0b11100000, 0b00001100,
+	0b00111100, 0b00011111, 0b11111000, 0b00011100,
+	0b00001111, 0b00111111, 0b11111100, 0b01111000,
+	0b00000010, 0b01111111, 0b11111110, 0b01100000,
+	0b00000000, 0b11111111, 0b11111111, 0b01000000,
+	0b00000000, 0b11111111, 0b11111111, 0b00000000,
+	0b00000001, 0b11111111, 0b11111111, 0b10000000,
+	0b00000001, 0b11111111, 0b11111111, 0b10000000,
+	0b11111001, 0b11111111, 0b11111111, 0b10011111,
+	0b00000001, 0b11111111, 0b11111111, 0b10011111,
+	0b00000001, 0b11111111, 0b11111111, 0b10000000,
+	0b00000001, 0b11111111, 0b11111111, 0b10000000,
+	0b00000000, 0b11111111, 0b11111111, 0b00000000,
+	0b00000110, 0b11111111, 0b11111111, 0b01100000,
+	0b00011110, 0b01111111, 0b11111110, 0b01110000,
+	0b00111000, 0b00111111, 0b11111100, 0b00111100,
+	0b00110000, 0b00011111, 0b11111000, 0b00001100,
+	0b00000000, 0b00000111, 0b11100000, 0b00000000,
+	0b00000000, 0b01110000, 0b00001110, 0b00000000,
+	0b00000000, 0b01100001, 0b10000110, 0b00000000,
+	0b00000000, 0b11100001, 0b10000011, 0b00000000,
+	0b00000000, 0b11000001, 0b10000011, 0b10000000,
+	0b00000001, 0b10000001, 0b10000001, 0b10000000,
+	0b00000000, 0b00000001, 0b10000000, 0b00000000,
+	0b00000000, 0b00000001, 0b10000000, 0b00000000,
+], 32);
+
 #[rustfmt::skip]
-pub const WIND: &[u8] = &[
+pub const WIND: ImageRaw<BinaryColor> = ImageRaw::new(&[
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
 	0b00000000, 0b00000011, 0b11000000, 0b00000000,
 	0b00000000, 0b00001111, 0b11110000, 0b00000000,
@@ -272,32 +558,4 @@ pub const WIND: &[u8] = &[
 	0b00000000, 0b01111111, 0b10000000, 0b00000000,
 	0b00000000, 0b00011110, 0b00000000, 0b00000000,
 	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
-#[rustfmt::skip]
-pub const WAVE: &[u8] = &[
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00001111, 0b00000000, 0b00000000,
-	0b00000000, 0b00111111, 0b10000000, 0b00000000,
-	0b00000000, 0b01111111, 0b11000000, 0b00000000,
-	0b00000000, 0b01111111, 0b11100000, 0b00000000,
-	0b00000000, 0b11111110, 0b00000000, 0b00000000,
-	0b00000000, 0b11111110, 0b00000000, 0b00000000,
-	0b00000001, 0b11111110, 0b00000000, 0b00000000,
-	0b00000001, 0b11111100, 0b00000000, 0b00000000,
-	0b00000001, 0b11111100, 0b00000000, 0b00000000,
-	0b00000011, 0b11111100, 0b00000000, 0b00000000,
-	0b00000011, 0b11111110, 0b00000000, 0b00000000,
-	0b00000111, 0b11111110, 0b00000000, 0b00000000,
-	0b00000111, 0b11111111, 0b00000000, 0b00000000,
-	0b00001111, 0b11111111, 0b10000000, 0b00000000,
-	0b00011111, 0b11111111, 0b11000000, 0b00000000,
-	0b00111111, 0b10011111, 0b11011111, 0b00000000,
-	0b00011110, 0b00001111, 0b00000111, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b01000000, 0b11100000, 0b01110000, 0b00000000,
-	0b01110011, 0b11111001, 0b11111100, 0b00000000,
-	0b00011110, 0b00001111, 0b00000111, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-	0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
+], 32);
@@ -1,10 +1,26 @@
+pub mod astronomy;
+pub mod cli;
 pub mod common;
 pub mod device_config;
 pub mod message;
+pub mod metrics;
 pub mod http;
 pub mod image_data;
+pub mod locale;
+pub mod logging;
+pub mod manifest;
+pub mod photo;
+pub mod preview;
+pub mod publish;
+pub mod refresh;
+pub mod reload;
+pub mod schedule;
+pub mod scoring;
 pub mod screen;
+pub mod serve;
+pub mod stale;
 pub mod surf_report_24h;
 pub mod surf_report_week;
 pub mod surfline_types;
-pub mod util;
+pub mod versions;
+pub mod wetsuit;
@@ -0,0 +1,260 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Language and date/time conventions of a device, set with `"locale"` in its configuration.
+/// Labels are drawn with the ISO 8859-1 fonts, so translations are limited to Latin-1.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// English with 24h times and month/day dates
+    #[default]
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "en-US")]
+    EnUs,
+    #[serde(rename = "en-GB")]
+    EnGb,
+    #[serde(rename = "es")]
+    Es,
+    #[serde(rename = "fr")]
+    Fr,
+    #[serde(rename = "de")]
+    De,
+    #[serde(rename = "pt")]
+    Pt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Clock {
+    TwentyFourHour,
+    TwelveHour,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateOrder {
+    MonthDay,
+    DayMonth,
+}
+
+/// Everything a screen writes on its own, as opposed to data from Surfline or the configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Messages {
+    pub clock: Clock,
+    pub date_order: DateOrder,
+    /// Separator between day and month, German dates also end with it ("24.12.")
+    pub date_separator: &'static str,
+    pub weekdays: [&'static str; 7],
+    pub updated: &'static str,
+    pub water: &'static str,
+    pub sunrise: &'static str,
+    pub sunset: &'static str,
+    pub moonrise: &'static str,
+    pub best_session: &'static str,
+    pub second_session: &'static str,
+    pub spring_tide: &'static str,
+    pub high_tide: &'static str,
+    pub low_tide: &'static str,
+    pub feet: &'static str,
+    pub knots: &'static str,
+    /// Stands in for a section whose data could not be fetched
+    pub no_data: &'static str,
+    /// Banner over a screen republished from its last good rendering
+    pub stale_since: &'static str,
+}
+
+const EN: Messages = Messages {
+    clock: Clock::TwentyFourHour,
+    date_order: DateOrder::MonthDay,
+    date_separator: "/",
+    weekdays: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    updated: "Updated:",
+    water: "Water",
+    sunrise: "Sunrise",
+    sunset: "Sunset",
+    moonrise: "Moonrise",
+    best_session: "Best",
+    second_session: "2nd",
+    spring_tide: "spring tide",
+    high_tide: "H",
+    low_tide: "L",
+    feet: "ft",
+    knots: "kt",
+    no_data: "no data",
+    stale_since: "STALE since",
+};
+
+const EN_US: Messages = Messages {
+    clock: Clock::TwelveHour,
+    ..EN
+};
+
+const EN_GB: Messages = Messages {
+    date_order: DateOrder::DayMonth,
+    ..EN
+};
+
+const ES: Messages = Messages {
+    clock: Clock::TwentyFourHour,
+    date_order: DateOrder::DayMonth,
+    date_separator: "/",
+    weekdays: ["Lun", "Mar", "Mié", "Jue", "Vie", "Sáb", "Dom"],
+    updated: "Actualizado:",
+    water: "Agua",
+    sunrise: "Amanecer",
+    sunset: "Atardecer",
+    moonrise: "Salida luna",
+    best_session: "Mejor",
+    second_session: "2º",
+    spring_tide: "mareas vivas",
+    high_tide: "P",
+    low_tide: "B",
+    feet: "ft",
+    knots: "kn",
+    no_data: "sin datos",
+    stale_since: "SIN ACTUALIZAR desde",
+};
+
+const FR: Messages = Messages {
+    clock: Clock::TwentyFourHour,
+    date_order: DateOrder::DayMonth,
+    date_separator: "/",
+    weekdays: ["Lun", "Mar", "Mer", "Jeu", "Ven", "Sam", "Dim"],
+    updated: "Mis à jour :",
+    water: "Eau",
+    sunrise: "Lever",
+    sunset: "Coucher",
+    moonrise: "Lever lune",
+    best_session: "Top",
+    second_session: "2e",
+    spring_tide: "vives-eaux",
+    high_tide: "PM",
+    low_tide: "BM",
+    feet: "ft",
+    knots: "nd",
+    no_data: "pas de données",
+    stale_since: "PÉRIMÉ depuis",
+};
+
+const DE: Messages = Messages {
+    clock: Clock::TwentyFourHour,
+    date_order: DateOrder::DayMonth,
+    date_separator: ".",
+    weekdays: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+    updated: "Aktualisiert:",
+    water: "Wasser",
+    sunrise: "Aufgang",
+    sunset: "Untergang",
+    moonrise: "Mondaufgang",
+    best_session: "Beste",
+    second_session: "2.",
+    spring_tide: "Springtide",
+    high_tide: "HW",
+    low_tide: "NW",
+    feet: "ft",
+    knots: "kn",
+    no_data: "keine Daten",
+    stale_since: "VERALTET seit",
+};
+
+const PT: Messages = Messages {
+    clock: Clock::TwentyFourHour,
+    date_order: DateOrder::DayMonth,
+    date_separator: "/",
+    weekdays: ["Seg", "Ter", "Qua", "Qui", "Sex", "Sáb", "Dom"],
+    updated: "Atualizado:",
+    water: "Água",
+    sunrise: "Nascer",
+    sunset: "Pôr",
+    moonrise: "Nascer lua",
+    best_session: "Melhor",
+    second_session: "2º",
+    spring_tide: "marés vivas",
+    high_tide: "PM",
+    low_tide: "BM",
+    feet: "ft",
+    knots: "nós",
+    no_data: "sem dados",
+    stale_since: "DESATUALIZADO desde",
+};
+
+impl Locale {
+    pub fn messages(&self) -> &'static Messages {
+        match self {
+            Locale::En => &EN,
+            Locale::EnUs => &EN_US,
+            Locale::EnGb => &EN_GB,
+            Locale::Es => &ES,
+            Locale::Fr => &FR,
+            Locale::De => &DE,
+            Locale::Pt => &PT,
+        }
+    }
+}
+
+impl Messages {
+    pub fn weekday(&self, weekday: Weekday) -> &'static str {
+        self.weekdays[weekday.num_days_from_monday() as usize]
+    }
+
+    /// Day and month without the year, e.g. "8/10" or "10.8."
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        let (first, second) = match self.date_order {
+            DateOrder::MonthDay => (date.month(), date.day()),
+            DateOrder::DayMonth => (date.day(), date.month()),
+        };
+        let trailing = if self.date_separator == "." { "." } else { "" };
+        format!("{}{}{}{}", first, self.date_separator, second, trailing)
+    }
+
+    /// Time of day, e.g. "6:05" or "6:05am"
+    pub fn format_time(&self, hour: u32, minute: u32) -> String {
+        match self.clock {
+            Clock::TwentyFourHour => format!("{}:{:02}", hour, minute),
+            Clock::TwelveHour => format!("{}:{:02}{}", twelve_hour(hour), minute, am_pm(hour)),
+        }
+    }
+
+    /// Hour on its own for chart axes, e.g. "18" or "6pm"
+    pub fn format_hour(&self, hour: u32) -> String {
+        match self.clock {
+            Clock::TwentyFourHour => format!("{}", hour),
+            Clock::TwelveHour => format!("{}{}", twelve_hour(hour), am_pm(hour)),
+        }
+    }
+
+    /// Range of whole hours, e.g. "06-09" or "6am-9am"
+    pub fn format_hour_range(&self, start: u32, end: u32) -> String {
+        match self.clock {
+            Clock::TwentyFourHour => format!("{:02}-{:02}", start, end),
+            Clock::TwelveHour => format!("{}-{}", self.format_hour(start), self.format_hour(end)),
+        }
+    }
+
+    /// Date and time of the last update in the footer
+    pub fn format_updated(&self, time: &NaiveDateTime) -> String {
+        format!("{} {}", self.updated, self.format_date_time(time))
+    }
+
+    /// Time of the last good rendering in the stale banner, e.g. "STALE since 8/10 06:00"
+    pub fn format_stale_since(&self, time: &NaiveDateTime) -> String {
+        format!("{} {}", self.stale_since, self.format_date_time(time))
+    }
+
+    fn format_date_time(&self, time: &NaiveDateTime) -> String {
+        let time_text = match self.clock {
+            Clock::TwentyFourHour => format!("{:02}:{:02}", time.hour(), time.minute()),
+            Clock::TwelveHour => self.format_time(time.hour(), time.minute()),
+        };
+        format!("{} {}", self.format_date(time.date()), time_text)
+    }
+}
+
+fn twelve_hour(hour: u32) -> u32 {
+    match hour % 12 {
+        0 => 12,
+        hour => hour,
+    }
+}
+
+fn am_pm(hour: u32) -> &'static str {
+    if hour < 12 { "am" } else { "pm" }
+}
@@ -0,0 +1,54 @@
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use std::{io::IsTerminal, path::Path};
+use tracing::{Span, Subscriber, info_span};
+use tracing_subscriber::{EnvFilter, fmt::MakeWriter, util::SubscriberInitExt};
+
+use crate::device_config::ScreenConfiguration;
+
+/// How log events are written to stdout
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum LogFormat {
+    /// One line per event with its span and fields, colored on a terminal
+    #[default]
+    Text,
+    /// One JSON object per event, for journald and log shippers
+    Json,
+}
+
+/// Install the global subscriber. `level` takes `RUST_LOG` directives, e.g. `info` or `warn,surfboard_scraper=debug`,
+/// and `RUST_LOG` overrides it when set
+pub fn init(level: &str, format: LogFormat) -> Result<()> {
+    subscriber(level, format, std::io::stdout)?
+        .try_init()
+        .map_err(|e| anyhow!("Failed to set up logging: {}", e))
+}
+
+/// The subscriber `init` installs, writing to `writer` instead of stdout
+pub fn subscriber<W>(level: &str, format: LogFormat, writer: W) -> Result<Box<dyn Subscriber + Send + Sync>>
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let filter = EnvFilter::try_from_default_env().or_else(|_| EnvFilter::try_new(level))?;
+    let builder = tracing_subscriber::fmt().with_env_filter(filter).with_writer(writer);
+    Ok(match format {
+        LogFormat::Text => Box::new(builder.with_ansi(std::io::stdout().is_terminal()).finish()),
+        // every event lists its spans with their fields, so a line can be filtered by device or screen on its own
+        LogFormat::Json => Box::new(builder.json().flatten_event(true).with_current_span(false).finish()),
+    })
+}
+
+/// Span around publishing the device config at `path`
+pub fn device_span(path: &Path) -> Span {
+    info_span!("device", config = %path.display())
+}
+
+/// Span around rendering and publishing a screen, with the surf spot it reports on
+pub fn screen_span(screen: &ScreenConfiguration) -> Span {
+    info_span!(
+        "screen",
+        id = screen.id,
+        url = screen.url,
+        spot_id = screen.params.get("spot_id").and_then(|spot_id| spot_id.as_str()),
+    )
+}
@@ -1,122 +1,289 @@
-mod common;
-mod http;
-mod image_data;
-mod screen;
-mod surf_report_24h;
-mod surf_report_week;
-mod surfline_types;
-mod util;
-
-use anyhow::Result;
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_s3::{Client, types::ObjectCannedAcl};
-use std::{collections::HashSet, io::Cursor};
-use surfboard_scraper::device_config::Configuration;
-use tokio::{
-    fs,
-    time::{Duration, sleep},
-};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Local, Utc};
+use clap::Parser;
 use glob::glob;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use surfboard_scraper::{
+    cli::{Cli, Command},
+    device_config::{Configuration, ScreenConfiguration},
+    logging::{device_span, screen_span},
+    manifest::{Manifest, ScreenManifest, manifest_key},
+    metrics::METRICS,
+    publish::{Connections, PublishConfig, Publisher, content_hash, put_if_changed},
+    refresh::{Scheduler, TICK},
+    reload::{Wakeup, Wakeups},
+    screen::RenderContext,
+    stale::{Rendered, StaleScreens},
+    versions::{RetiredVersions, versioned_key},
+};
+use tokio::fs;
+use tracing::{Instrument, error, info, warn};
 
-use crate::util::parse_s3_url;
-
-pub async fn upload_object(
-    client: &aws_sdk_s3::Client,
-    bucket_name: &str,
-    content: &str,
-    key: &str,
-) -> Result<aws_sdk_s3::operation::put_object::PutObjectOutput> {
-    Ok(client
-        .put_object()
-        .bucket(bucket_name)
-        .key(key)
-        .body(aws_sdk_s3::primitives::ByteStream::from(content.as_bytes().to_vec()))
-        .acl(ObjectCannedAcl::PublicRead)
-        .send()
-        .await
-        .unwrap())
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    surfboard_scraper::logging::init(&cli.log_level, cli.log_format)?;
+    let config_directory = cli.config_directory;
+    let state_file = cli.state_file;
+    match cli.command.unwrap_or(Command::Daemon) {
+        Command::Render(args) => surfboard_scraper::cli::render(&config_directory, &args).await,
+        Command::Validate => {
+            let problems = surfboard_scraper::cli::validate(&config_directory)?;
+            problems.iter().for_each(|problem| error!("{}", problem));
+            if !problems.is_empty() {
+                return Err(anyhow!("{} problems with the device configs", problems.len()));
+            }
+            info!(?config_directory, "Device configs are valid");
+            Ok(())
+        }
+        Command::Publish(args) if args.dry_run => surfboard_scraper::cli::dry_run(&config_directory).await,
+        Command::Publish(args) if args.once => publish(config_directory, state_file, true).await,
+        Command::Publish(_) | Command::Daemon => tokio::select! {
+            metrics = surfboard_scraper::metrics::serve_or_log(cli.metrics_port) => metrics,
+            published = publish(config_directory, state_file, false) => published,
+        },
+        Command::Preview(args) => surfboard_scraper::preview::serve(args.into_options(config_directory)).await,
+        // publishes as usual and also serves the directory to the devices, for configs with a "directory" publish
+        // target
+        Command::Serve(args) => tokio::select! {
+            served = surfboard_scraper::serve::serve(args.into_options()) => served,
+            metrics = surfboard_scraper::metrics::serve_or_log(cli.metrics_port) => metrics,
+            published = publish(config_directory, state_file, false) => published,
+        },
+    }
 }
 
-pub async fn upload_bytes(
-    client: &aws_sdk_s3::Client,
-    bucket_name: &str,
-    content: &[u8],
-    key: &str,
-) -> Result<aws_sdk_s3::operation::put_object::PutObjectOutput> {
-    Ok(client
-        .put_object()
-        .bucket(bucket_name)
-        .key(key)
-        .body(aws_sdk_s3::primitives::ByteStream::from(content.to_vec()))
-        .acl(ObjectCannedAcl::PublicRead)
-        .send()
-        .await
-        .unwrap())
+/// What the publishing loop remembers from one cycle to the next
+#[derive(Default)]
+struct PublishHistory {
+    // last good image of every screen, republished with a stale banner when a screen keeps failing
+    stale_screens: StaleScreens,
+    // manifest entry of the version currently published for every configured screen URL, a screen that fails keeps
+    // its last one
+    published: HashMap<String, ScreenManifest>,
+    // versions no config points at anymore, deleted after a grace period
+    retired_versions: RetiredVersions,
+    // publish target of every device config
+    connections: Connections,
+    // when every screen URL renders again
+    scheduler: Scheduler,
 }
 
-const CONFIG_DIRECTORY: &'static str = "deploy/configs";
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    // TODO: graceful shutdown
-
-    // initialize AWS S3 client
-    let region_provider = RegionProviderChain::default_provider().or_else("us-east-1");
-    let config = aws_config::from_env()
-        .region(region_provider)
-        .profile_name("yurigorokhov")
-        .load()
-        .await;
-    let client = Client::new(&config);
+/// Publish the screens of every device config as their refresh policies come due, right away when a config
+/// changes or on SIGHUP, until SIGTERM or Ctrl-C. With `once` every screen publishes a single time and it returns.
+/// Retired versions are saved to `state_file` after every cycle
+async fn publish(config_directory: PathBuf, state_file: PathBuf, once: bool) -> Result<()> {
+    let mut wakeups = Wakeups::new(&config_directory)?;
+    let mut history = PublishHistory {
+        // a lost state file only restarts the grace periods
+        retired_versions: RetiredVersions::load(&state_file).unwrap_or_else(|e| {
+            warn!("Starting without retired versions: {:#}", e);
+            RetiredVersions::default()
+        }),
+        ..PublishHistory::default()
+    };
 
     loop {
+        let now = Local::now();
 
         // cache output URLs so we don't process the same surf spots over and over
-        let mut already_processed_screen_urls: HashSet<String> = HashSet::new();
+        let mut processed_urls: HashSet<String> = HashSet::new();
 
-        // parse config files and upload surf reports
-        for entry in glob(format!("{}/*.json", CONFIG_DIRECTORY).as_str()).expect("Failed to read glob pattern") {
-            let path = entry?;
-            let config: Configuration = serde_json::from_str(fs::read_to_string(&path).await?.as_str())?;
-            for screen in config.screens {
-                if !already_processed_screen_urls.contains(&screen.url) {
-                    let mut bytes: Vec<u8> = Vec::new();
-                    screen.draw_to_qoi(&mut Cursor::new(&mut bytes)).await?;
-
-                    // upload screen image
-                    match parse_s3_url(&screen.url) {
-                        Ok((bucket, path)) => {
-                            upload_bytes(&client, &bucket, &bytes, &path).await?;
-                            println!("Uploaded: {} bytes: {}", screen.url, &bytes.len());
-                        }
-                        Err(e) => {
-                            println!("Error: {:#?}", e);
-                        }
+        // parse config files and upload surf reports, a broken device doesn't hold up the others
+        let pattern = config_directory.join("*.json");
+        for entry in glob(&pattern.to_string_lossy())? {
+            // let the device in flight finish its uploads, then stop before starting the next
+            if wakeups.shutdown_requested() {
+                break;
+            }
+            match entry {
+                Ok(path) => {
+                    let span = device_span(&path);
+                    let result = publish_device(&path, &mut processed_urls, &mut history, now)
+                        .instrument(span.clone())
+                        .await;
+                    if let Err(e) = result {
+                        error!(parent: &span, "Failed to publish device: {:#}", e);
                     }
-                    already_processed_screen_urls.insert(screen.url);
                 }
+                Err(e) => error!(path = ?e.path(), "Failed to read device config: {}", e.error()),
+            }
+        }
+        if let Err(e) = history.retired_versions.save(&state_file) {
+            error!("Failed to save retired versions: {:#}", e);
+        }
+        if once {
+            return Ok(());
+        }
+        match wakeups.wait(TICK).await? {
+            Wakeup::Tick => {}
+            Wakeup::Reload => info!("Reloading device configs"),
+            Wakeup::Shutdown => {
+                info!("Shutting down");
+                return Ok(());
             }
+        }
+    }
+}
 
-            // upload screensaver
-            if let Some(screen_saver) = config.screen_saver {
-                let mut bytes: Vec<u8> = Vec::new();
-                screen_saver.draw_to_qoi(&mut Cursor::new(&mut bytes)).await?;
-                match parse_s3_url(&screen_saver.url) {
-                    Ok((bucket, path)) => {
-                        upload_bytes(&client, &bucket, &bytes, &path).await?;
-                        println!("Uploaded: {} bytes: {}", screen_saver.url, &bytes.len());
-                    }
-                    Err(e) => {
-                        println!("Error: {:#?}", e);
+/// Publish a device's screens that are due, then its manifest and last the config that points at the new versions.
+/// A screen that fails to render or upload is logged and skipped
+async fn publish_device(
+    path: &Path,
+    processed_urls: &mut HashSet<String>,
+    history: &mut PublishHistory,
+    now: DateTime<Local>,
+) -> Result<()> {
+    let PublishHistory {
+        stale_screens,
+        published,
+        retired_versions,
+        connections,
+        scheduler,
+    } = history;
+    let config: Configuration = serde_json::from_str(fs::read_to_string(path).await?.as_str())?;
+    let publish = config
+        .publish
+        .clone()
+        .ok_or(anyhow!("No \"publish\" target in {:?}", path))?;
+    let config_key = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or(anyhow!("Invalid device config file name {:?}", path))?;
+    let target = connections.connect(config_key, &publish).await;
+    let manifest_key = manifest_key(config_key);
+
+    // old versions are collected for every screen whatever its schedule, and for screens the config had before
+    let configured_urls = config.all_screens().map(|screen| screen.url.as_str());
+    let collected_urls = retired_versions.screens_of(config_key, configured_urls);
+
+    // only render and publish the screens that are scheduled for today
+    let mut config = config.active_on(now.date_naive());
+    let context = config.render_context();
+
+    // after a restart, and on every `--once` run, pick up the versions an earlier run published from its manifest,
+    // so a screen that fails keeps pointing at its last image and can fall back to it as stale
+    if config.all_screens().any(|screen| !published.contains_key(&screen.url)) {
+        match Manifest::fetch(target, &manifest_key).await {
+            Ok(manifest) => {
+                let restored = manifest
+                    .map(|manifest| manifest.published(&publish, &config))
+                    .unwrap_or_default();
+                for screen in config.all_screens() {
+                    let Some(entry) = restored.get(&screen.url) else {
+                        continue;
+                    };
+                    if let Err(e) = stale_screens.restore(screen, entry, &publish, target).await {
+                        warn!(url = screen.url, "Failed to read the last published image: {:#}", e);
                     }
+                    published.entry(screen.url.clone()).or_insert(entry.clone());
+                }
+            }
+            Err(e) => warn!("Failed to read the published manifest: {:#}", e),
+        }
+    }
+    for screen in config.all_screens() {
+        if processed_urls.insert(screen.url.clone()) && scheduler.is_due(screen, &now) {
+            let span = screen_span(screen);
+            let result = publish_screen(&publish, target, &context, screen, stale_screens)
+                .instrument(span.clone())
+                .await;
+            scheduler.ran(screen, &now, result.is_ok());
+            match result {
+                Ok(manifest) => {
+                    published.insert(screen.url.clone(), manifest);
                 }
+                // the last image stays up until the screen is tried again
+                Err(e) => error!(parent: &span, "Failed to publish screen: {:#}", e),
             }
+        }
+        if let Some(manifest) = published.get_mut(&screen.url) {
+            manifest.next_refresh_at = scheduler.next_run(screen, &now);
+        }
+    }
+
+    // upload the manifest the device times its refreshes from
+    let manifest = serde_json::to_vec(&Manifest::new(&config, published))?;
+    put_if_changed(target, &manifest_key, manifest)
+        .await
+        .inspect_err(|_| METRICS.upload_failures.with_label_values(&[&manifest_key]).inc())?;
+
+    // point the device-facing config at the published versions, devices skip the download and the refresh while
+    // the hash stays the same
+    for screen in config.screens.iter_mut().chain(config.screen_saver.iter_mut()) {
+        if let Some(manifest) = published.get(&screen.url) {
+            screen.url = manifest.url.clone();
+            screen.hash = Some(manifest.hash.clone());
+        }
+    }
+    let config_str = serde_json::to_string(&config)?;
+    let size = config_str.len();
+    let uploaded = put_if_changed(target, config_key, config_str.into_bytes())
+        .await
+        .inspect_err(|_| METRICS.upload_failures.with_label_values(&[config_key]).inc())?;
+    if uploaded {
+        info!(bytes = size, "Uploaded config");
+    }
 
-            // upload the configs themselves
-            let config_str = fs::read(&path).await?;
-            upload_bytes(&client, "yurig-public", config_str.as_slice(), path.file_name().unwrap().to_str().unwrap()).await?;
-            println!("Uploaded config, bytes: {}", config_str.as_slice().len());
+    // delete versions nothing has pointed at for a while, a device may still hold an older config. Only screens active
+    // today on a device published so far this cycle are live, a screen scheduled off keeps its last entry in
+    // `published` for when it's back
+    let live_urls: HashSet<String> = published
+        .iter()
+        .filter(|(screen_url, _)| processed_urls.contains(*screen_url))
+        .map(|(_, manifest)| manifest.url.clone())
+        .collect();
+    for screen_url in &collected_urls {
+        match retired_versions
+            .collect(&publish, target, screen_url, &live_urls, Utc::now())
+            .await
+        {
+            Ok(deleted) => deleted.iter().for_each(|url| info!(url, "Deleted old version")),
+            Err(e) => error!(url = screen_url, "Failed to delete old versions: {:#}", e),
         }
-        sleep(Duration::from_secs(3600 * 3)).await;
     }
+    Ok(())
+}
+
+/// Render a screen, or its stale fallback, and publish it under a content-addressed version of the key its `url`
+/// points at unless that version is already there, returns its manifest entry
+async fn publish_screen(
+    publish: &PublishConfig,
+    target: &Publisher,
+    context: &RenderContext,
+    screen: &ScreenConfiguration,
+    stale_screens: &mut StaleScreens,
+) -> Result<ScreenManifest> {
+    let started = Instant::now();
+    let Rendered { bytes, stale } = stale_screens.render(screen, context).await?;
+    let size = bytes.len();
+    // a stale fallback isn't a render, the metrics keep showing how long ago the screen last was one
+    if !stale {
+        METRICS.rendered(&screen.url, started.elapsed(), size);
+    }
+    let hash = content_hash(&bytes);
+    let unversioned_key = publish.key(&screen.url)?;
+    let key = versioned_key(&unversioned_key, &hash);
+    let url = format!("{}{}", publish.public_url(), key);
+    let uploaded = put_if_changed(target, &key, bytes)
+        .await
+        .inspect_err(|_| METRICS.upload_failures.with_label_values(&[&unversioned_key]).inc())?;
+    if !stale {
+        METRICS.published(&screen.url, Utc::now());
+    }
+    if uploaded {
+        info!(version = url, bytes = size, "Uploaded");
+    } else {
+        info!(version = url, "Unchanged");
+    }
+    let rendered_at = context.now();
+    let valid_from = stale_screens.fresh_since(&screen.url).unwrap_or(rendered_at);
+    Ok(ScreenManifest {
+        url,
+        ..ScreenManifest::new(screen, hash, rendered_at, valid_from, None)
+    })
 }
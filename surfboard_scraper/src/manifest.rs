@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use chrono::{
+    DateTime, TimeDelta, Utc,
+    serde::{ts_seconds, ts_seconds_option},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    device_config::{Configuration, ScreenConfiguration},
+    publish::{PublishConfig, PublishTarget},
+    versions::versioned_key,
+};
+
+/// Published next to a device's `<device>.json` as `<device>.manifest.json`. Times are Unix seconds so a device
+/// can read them without a date parser
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub screens: Vec<ScreenManifest>,
+    pub screen_saver: Option<ScreenManifest>,
+
+    // earliest `next_refresh_at` of the screens, when the device should wake up and check again
+    #[serde(with = "ts_seconds_option")]
+    pub next_refresh_at: Option<DateTime<Utc>>,
+}
+
+/// A published image and how long the data drawn on it is good for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreenManifest {
+    pub url: String,
+    // `content_hash` of the image
+    pub hash: String,
+    #[serde(with = "ts_seconds")]
+    pub rendered_at: DateTime<Utc>,
+    // the data is current from its last good rendering until it's republished as stale
+    #[serde(with = "ts_seconds")]
+    pub valid_from: DateTime<Utc>,
+    #[serde(with = "ts_seconds")]
+    pub valid_until: DateTime<Utc>,
+    // when the scraper renders the screen again, None when only a change to its configuration does
+    #[serde(with = "ts_seconds_option")]
+    pub next_refresh_at: Option<DateTime<Utc>>,
+}
+
+impl ScreenManifest {
+    /// Entry for an image of `screen` rendered at `rendered_at` from data that was last good at `valid_from`
+    pub fn new(
+        screen: &ScreenConfiguration,
+        hash: String,
+        rendered_at: DateTime<Utc>,
+        valid_from: DateTime<Utc>,
+        next_refresh_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        ScreenManifest {
+            url: screen.url.clone(),
+            hash,
+            rendered_at,
+            valid_from,
+            valid_until: valid_from + TimeDelta::hours(screen.stale_after_hours.into()),
+            next_refresh_at,
+        }
+    }
+}
+
+impl Manifest {
+    /// Manifest of a device-facing config from the published screens by URL, screens that never published are left out
+    pub fn new(config: &Configuration, published: &HashMap<String, ScreenManifest>) -> Self {
+        let entry = |screen: &ScreenConfiguration| published.get(&screen.url).cloned();
+        let screens: Vec<ScreenManifest> = config.screens.iter().filter_map(entry).collect();
+        let screen_saver = config.screen_saver.as_ref().and_then(entry);
+        let next_refresh_at = screens
+            .iter()
+            .chain(screen_saver.iter())
+            .filter_map(|screen| screen.next_refresh_at)
+            .min();
+        Manifest {
+            screens,
+            screen_saver,
+            next_refresh_at,
+        }
+    }
+
+    /// The manifest last published under `key`, None when there isn't one
+    pub async fn fetch<T: PublishTarget>(target: &T, key: &str) -> Result<Option<Self>> {
+        let Some(bytes) = target.get(key).await? else {
+            return Ok(None);
+        };
+        let manifest = serde_json::from_slice(&bytes).with_context(|| format!("Failed to parse manifest {}", key))?;
+        Ok(Some(manifest))
+    }
+
+    /// Entries of the screens of `config` by their configured URL, the inverse of `new`, so a restarted scraper
+    /// keeps pointing at the versions an earlier run published
+    pub fn published(&self, publish: &PublishConfig, config: &Configuration) -> HashMap<String, ScreenManifest> {
+        let entries: Vec<&ScreenManifest> = self.screens.iter().chain(self.screen_saver.iter()).collect();
+        let mut published = HashMap::new();
+        for screen in config.all_screens() {
+            let Ok(key) = publish.key(&screen.url) else {
+                continue;
+            };
+            let entry = entries
+                .iter()
+                .find(|entry| entry.url == format!("{}{}", publish.public_url(), versioned_key(&key, &entry.hash)));
+            if let Some(entry) = entry {
+                published.insert(screen.url.clone(), (*entry).clone());
+            }
+        }
+        published
+    }
+}
+
+/// Key of the manifest published next to the device config under `config_key`, `0001.json` -> `0001.manifest.json`
+pub fn manifest_key(config_key: &str) -> String {
+    format!(
+        "{}.manifest.json",
+        config_key.strip_suffix(".json").unwrap_or(config_key)
+    )
+}
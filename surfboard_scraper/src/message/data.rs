@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use core::fmt::Debug;
 use embedded_graphics::prelude::*;
 use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay};
@@ -8,22 +8,74 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{message::draw::draw, screen::Screen};
+use crate::{
+    common::{bitmap::TriColorBitmap, draw_utils::qr_code_size},
+    message::draw::{IMAGE_MAX_HEIGHT, IMAGE_MAX_WIDTH, QR_CODE_MODULE_SIZE, draw},
+    screen::{RenderContext, Screen},
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FontSize {
+    Small,
+    Medium,
+    Large,
+    XLarge,
+    Huge,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MessageParams {
     pub message: String,
+    pub title: Option<String>,
+    pub title_size: FontSize,
+    pub body_size: FontSize,
+    pub alignment: TextAlignment,
+    pub image: Option<String>,
+    pub border: bool,
+    pub qr_code: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MessageData {
     pub message: String,
+    pub title: Option<String>,
+    pub title_size: FontSize,
+    pub body_size: FontSize,
+    pub alignment: TextAlignment,
+    pub image: Option<TriColorBitmap>,
+    pub border: bool,
+    // e.g. `WIFI:T:WPA;S:<ssid>;P:<password>;;` for guest Wi-Fi credentials
+    pub qr_code: Option<String>,
 }
 
 impl Screen<MessageParams> for MessageData {
-    async fn from_params(params: &MessageParams) -> Result<Box<Self>> {
+    async fn from_params(params: &MessageParams, _context: &RenderContext) -> Result<Box<Self>> {
+        let image = match &params.image {
+            Some(path) => Some(TriColorBitmap::load_thresholded(
+                path,
+                IMAGE_MAX_WIDTH,
+                IMAGE_MAX_HEIGHT,
+            )?),
+            None => None,
+        };
         Ok(Box::new(MessageData {
             message: params.message.clone(),
+            title: params.title.clone(),
+            title_size: params.title_size,
+            body_size: params.body_size,
+            alignment: params.alignment,
+            image,
+            border: params.border,
+            qr_code: params.qr_code.clone(),
         }))
     }
 
@@ -36,7 +88,7 @@ impl Screen<MessageParams> for MessageData {
         let output_settings = OutputSettingsBuilder::new().scale(1).build();
         let output_image = display.to_rgb_output_image(&output_settings);
         let image_buffer = output_image.as_image_buffer();
-        image_buffer.write_to(writer, image::ImageFormat::Qoi).unwrap();
+        image_buffer.write_to(writer, image::ImageFormat::Qoi)?;
         Ok(())
     }
 
@@ -44,8 +96,37 @@ impl Screen<MessageParams> for MessageData {
         let message = params
             .get("message")
             .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-        Ok(MessageParams { message: message.unwrap() })
+            .map(|s| s.to_string())
+            .ok_or(anyhow!("Missing message"))?;
+        let title = params.get("title").and_then(|v| v.as_str()).map(|s| s.to_string());
+        let title_size = match params.get("title_size") {
+            Some(v) => serde_json::from_value(v.clone())?,
+            None => FontSize::XLarge,
+        };
+        let body_size = match params.get("body_size") {
+            Some(v) => serde_json::from_value(v.clone())?,
+            None => FontSize::Large,
+        };
+        let alignment = match params.get("alignment") {
+            Some(v) => serde_json::from_value(v.clone())?,
+            None => TextAlignment::Center,
+        };
+        let image = params.get("image").and_then(|v| v.as_str()).map(|s| s.to_string());
+        let border = params.get("border").and_then(|v| v.as_bool()).unwrap_or(false);
+        let qr_code = params.get("qr_code").and_then(|v| v.as_str()).map(|s| s.to_string());
+        if let Some(qr_code) = &qr_code {
+            qr_code_size(qr_code, QR_CODE_MODULE_SIZE)?;
+        }
+        Ok(MessageParams {
+            message,
+            title,
+            title_size,
+            body_size,
+            alignment,
+            image,
+            border,
+            qr_code,
+        })
     }
 
     fn draw<D, E>(&self, target: &mut D) -> Result<(), E>
@@ -1,26 +1,166 @@
-use crate::message::data::MessageData;
+use crate::common::draw_utils::{ScaledDrawTarget, draw_qr_code, qr_code_size, wrap_text};
+use crate::message::data::{FontSize, MessageData, TextAlignment};
 use core::fmt::Debug;
-use embedded_graphics::mono_font::ascii::FONT_10X20;
-use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::mono_font::iso_8859_1::{FONT_6X10, FONT_9X15, FONT_10X20};
+use embedded_graphics::mono_font::{MonoFont, MonoTextStyle};
 use embedded_graphics::prelude::*;
-use embedded_graphics::text::{Alignment, Text};
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
 use epd_waveshare::color::TriColor;
 
+const SCREEN_WIDTH: i32 = 800;
+const SCREEN_HEIGHT: i32 = 480;
+const MARGIN: i32 = 40;
+const CONTENT_WIDTH: i32 = SCREEN_WIDTH - 2 * MARGIN;
+const SECTION_GAP: i32 = 20;
+
+pub const IMAGE_MAX_WIDTH: u32 = CONTENT_WIDTH as u32;
+pub const IMAGE_MAX_HEIGHT: u32 = 200;
+pub const QR_CODE_MODULE_SIZE: u32 = 4;
+
+/// Font and integer magnification used for a given font size
+fn font_for_size(size: FontSize) -> (&'static MonoFont<'static>, i32) {
+    match size {
+        FontSize::Small => (&FONT_6X10, 1),
+        FontSize::Medium => (&FONT_9X15, 1),
+        FontSize::Large => (&FONT_10X20, 1),
+        FontSize::XLarge => (&FONT_10X20, 2),
+        FontSize::Huge => (&FONT_10X20, 3),
+    }
+}
+
+/// Block of word-wrapped lines drawn in a single font
+struct TextBlock {
+    lines: Vec<String>,
+    font: &'static MonoFont<'static>,
+    scale: i32,
+}
+
+impl TextBlock {
+    fn new(text: &str, size: FontSize) -> Self {
+        let (font, scale) = font_for_size(size);
+        let char_width = (font.character_size.width + font.character_spacing) as i32 * scale;
+        TextBlock {
+            lines: wrap_text(text, (CONTENT_WIDTH / char_width) as usize),
+            font,
+            scale,
+        }
+    }
+
+    fn line_height(&self) -> i32 {
+        (self.font.character_size.height as i32 + 4) * self.scale
+    }
+
+    fn height(&self) -> i32 {
+        self.lines.len() as i32 * self.line_height()
+    }
+
+    /// Drop lines that don't fit in `max_height`, marking the cut with an ellipsis
+    fn truncate(&mut self, max_height: i32) {
+        let max_lines = (max_height / self.line_height()).max(1) as usize;
+        if self.lines.len() > max_lines {
+            self.lines.truncate(max_lines);
+            if let Some(last) = self.lines.last_mut() {
+                last.push_str("...");
+            }
+        }
+    }
+
+    fn draw<D, E>(&self, target: &mut D, x: i32, y: i32, alignment: Alignment) -> Result<(), E>
+    where
+        E: Debug,
+        D: DrawTarget<Color = TriColor, Error = E>,
+    {
+        let text_style = TextStyleBuilder::new()
+            .alignment(alignment)
+            .baseline(Baseline::Top)
+            .build();
+        for (idx, line) in self.lines.iter().enumerate() {
+            let position = Point::new(x, y + idx as i32 * self.line_height());
+            let text = Text::with_text_style(
+                line.as_str(),
+                position,
+                MonoTextStyle::new(self.font, TriColor::Black),
+                text_style,
+            );
+            if self.scale > 1 {
+                text.draw(&mut ScaledDrawTarget::new(target, position, self.scale as u32))?;
+            } else {
+                text.draw(target)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 pub fn draw<D, E>(target: &mut D, data: &MessageData) -> Result<(), E>
 where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    let style = MonoTextStyle::new(&FONT_10X20, TriColor::Black);
+    if data.border {
+        draw_border(target)?;
+    }
+
+    let title = data.title.as_ref().map(|title| TextBlock::new(title, data.title_size));
+    let mut body = TextBlock::new(&data.message, data.body_size);
 
-    // Draw "Happy Birthday" centered
-    Text::with_alignment(
-        data.message.as_str(),
-        Point::new(400, 200),
-        style,
-        Alignment::Center,
-    )
-    .draw(target)?;
+    // work out the total height of the card so it can be centered vertically
+    let image_height = data.image.as_ref().map(|i| i.height as i32 + SECTION_GAP).unwrap_or(0);
+    let title_height = title.as_ref().map(|t| t.height() + SECTION_GAP).unwrap_or(0);
+    // checked when the params are parsed, a QR code that doesn't fit is left out
+    let qr_code_side = data
+        .qr_code
+        .as_ref()
+        .and_then(|qr_code| qr_code_size(qr_code, QR_CODE_MODULE_SIZE).ok())
+        .map(|side| side as i32);
+    let qr_code_height = qr_code_side.map(|side| side + SECTION_GAP).unwrap_or(0);
+    body.truncate(SCREEN_HEIGHT - 2 * MARGIN - image_height - title_height - qr_code_height);
+    let total_height = image_height + title_height + body.height() + qr_code_height;
+    let mut y = ((SCREEN_HEIGHT - total_height) / 2).max(MARGIN);
 
+    // x of a block of the given width, following the text alignment
+    let block_x = |width: i32| match data.alignment {
+        TextAlignment::Left => MARGIN,
+        TextAlignment::Center => (SCREEN_WIDTH - width) / 2,
+        TextAlignment::Right => SCREEN_WIDTH - MARGIN - width,
+    };
+    let (text_x, alignment) = match data.alignment {
+        TextAlignment::Left => (MARGIN, Alignment::Left),
+        TextAlignment::Center => (SCREEN_WIDTH / 2, Alignment::Center),
+        TextAlignment::Right => (SCREEN_WIDTH - MARGIN, Alignment::Right),
+    };
+
+    if let Some(image) = &data.image {
+        image.draw(Point::new(block_x(image.width as i32), y), target)?;
+        y += image_height;
+    }
+
+    if let Some(title) = &title {
+        title.draw(target, text_x, y, alignment)?;
+        y += title_height;
+    }
+
+    body.draw(target, text_x, y, alignment)?;
+    y += body.height() + SECTION_GAP;
+
+    if let (Some(qr_code), Some(side)) = (&data.qr_code, qr_code_side) {
+        draw_qr_code(target, qr_code, Point::new(block_x(side), y), QR_CODE_MODULE_SIZE)?;
+    }
+
+    Ok(())
+}
+
+fn draw_border<D, E>(target: &mut D) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    Rectangle::new(Point::new(10, 10), Size::new(780, 460))
+        .into_styled(PrimitiveStyle::with_stroke(TriColor::Chromatic, 4))
+        .draw(target)?;
+    Rectangle::new(Point::new(20, 20), Size::new(760, 440))
+        .into_styled(PrimitiveStyle::with_stroke(TriColor::Black, 1))
+        .draw(target)?;
     Ok(())
 }
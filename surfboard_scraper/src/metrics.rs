@@ -0,0 +1,122 @@
+use anyhow::Result;
+use axum::{
+    Router,
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder};
+use std::{net::SocketAddr, sync::LazyLock, time::Duration};
+use tracing::{error, info};
+
+/// Port `/metrics` is served on while the scraper publishes continuously, next to Prometheus' exporter ports
+pub const DEFAULT_METRICS_PORT: u16 = 9184;
+
+/// What the scraper has been doing, scraped by Prometheus from `/metrics`
+pub struct Metrics {
+    registry: Registry,
+    // Surfline API calls by endpoint, e.g. `wave`
+    pub fetch_seconds: HistogramVec,
+    pub fetch_errors: IntCounterVec,
+    // by screen URL
+    pub render_seconds: HistogramVec,
+    pub render_failures: IntCounterVec,
+    pub encoded_bytes: IntGaugeVec,
+    pub last_publish: IntGaugeVec,
+    // by unversioned key
+    pub upload_failures: IntCounterVec,
+}
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(|| Metrics::new().expect("Invalid metric"));
+
+impl Metrics {
+    fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new_custom(Some("surfboard".to_string()), None)?;
+        let histogram = |name: &str, help: &str, label: &str| -> prometheus::Result<HistogramVec> {
+            let histogram = HistogramVec::new(HistogramOpts::new(name, help), &[label])?;
+            registry.register(Box::new(histogram.clone()))?;
+            Ok(histogram)
+        };
+        let counter = |name: &str, help: &str, label: &str| -> prometheus::Result<IntCounterVec> {
+            let counter = IntCounterVec::new(Opts::new(name, help), &[label])?;
+            registry.register(Box::new(counter.clone()))?;
+            Ok(counter)
+        };
+        let gauge = |name: &str, help: &str, label: &str| -> prometheus::Result<IntGaugeVec> {
+            let gauge = IntGaugeVec::new(Opts::new(name, help), &[label])?;
+            registry.register(Box::new(gauge.clone()))?;
+            Ok(gauge)
+        };
+        Ok(Metrics {
+            fetch_seconds: histogram("fetch_duration_seconds", "Surfline API request latency", "endpoint")?,
+            fetch_errors: counter("fetch_errors_total", "Failed Surfline API requests", "endpoint")?,
+            render_seconds: histogram("render_duration_seconds", "Time to render and encode a screen", "url")?,
+            render_failures: counter("render_failures_total", "Screens that failed to render", "url")?,
+            encoded_bytes: gauge("encoded_bytes", "Size of the last image published for a screen", "url")?,
+            last_publish: gauge(
+                "last_publish_timestamp_seconds",
+                "Unix time a screen was last published",
+                "url",
+            )?,
+            upload_failures: counter("upload_failures_total", "Failed uploads to a publish target", "key")?,
+            registry,
+        })
+    }
+
+    pub fn fetched(&self, endpoint: &str, duration: Duration, succeeded: bool) {
+        self.fetch_seconds
+            .with_label_values(&[endpoint])
+            .observe(duration.as_secs_f64());
+        if !succeeded {
+            self.fetch_errors.with_label_values(&[endpoint]).inc();
+        }
+    }
+
+    pub fn rendered(&self, url: &str, duration: Duration, size: usize) {
+        self.render_seconds
+            .with_label_values(&[url])
+            .observe(duration.as_secs_f64());
+        self.encoded_bytes.with_label_values(&[url]).set(size as i64);
+    }
+
+    pub fn published(&self, url: &str, at: DateTime<Utc>) {
+        self.last_publish.with_label_values(&[url]).set(at.timestamp());
+    }
+
+    /// Every metric in the Prometheus text format
+    pub fn encode(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+/// Serve `/metrics` on every interface until the process is stopped, so Prometheus can scrape it from elsewhere
+pub async fn serve(port: u16) -> Result<()> {
+    let address = SocketAddr::from(([0, 0, 0, 0], port));
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    info!("Metrics on http://{}/metrics", address);
+    axum::serve(listener, router()).await?;
+    Ok(())
+}
+
+/// `serve` next to publishing, a port that's taken is logged and publishing carries on without metrics. Never
+/// returns, so it can't end the `select!` it runs in
+pub async fn serve_or_log(port: u16) -> Result<()> {
+    if let Err(e) = serve(port).await {
+        error!(port, "Failed to serve metrics: {:#}", e);
+    }
+    std::future::pending().await
+}
+
+pub fn router() -> Router {
+    Router::new().route("/metrics", get(metrics))
+}
+
+async fn metrics() -> impl IntoResponse {
+    match METRICS.encode() {
+        Ok(text) => ([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], text).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e)).into_response(),
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::{Result, anyhow};
+use core::fmt::Debug;
+use embedded_graphics::prelude::*;
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay};
+use epd_waveshare::color::TriColor;
+use image::imageops::FilterType;
+use rand::seq::IndexedRandom;
+use serde_json::Value;
+use std::{collections::HashMap, io::Cursor, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::bitmap::TriColorBitmap,
+    photo::draw::draw,
+    screen::{RenderContext, SERVER_SIDE_IMAGE_BYTES, Screen},
+};
+
+const PHOTO_EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "png"];
+
+// QOI pays for every color change along a row, so dithered photos rarely fit the device buffer at full detail.
+// Settings are tried in order, weakening the error diffusion and then coarsening the pixels, until one fits.
+const DITHER_PIXEL_SIZES: [u32; 4] = [1, 2, 4, 8];
+const DITHER_STRENGTHS: [f32; 4] = [1.0, 0.5, 0.25, 0.0];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PhotoParams {
+    pub path: String,
+    pub chromatic: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PhotoData {
+    pub path: String,
+    pub pixel_size: u32,
+    pub dither_strength: f32,
+    pub bitmap: TriColorBitmap,
+}
+
+impl Screen<PhotoParams> for PhotoData {
+    async fn from_params(params: &PhotoParams, _context: &RenderContext) -> Result<Box<Self>> {
+        let path = pick_photo(&params.path)?;
+        let photo = image::open(&path)?.resize_to_fill(800, 480, FilterType::Lanczos3);
+        for pixel_size in DITHER_PIXEL_SIZES {
+            let downscaled = photo
+                .resize_exact(800 / pixel_size, 480 / pixel_size, FilterType::Triangle)
+                .to_rgb8();
+            for dither_strength in DITHER_STRENGTHS {
+                let data = PhotoData {
+                    path: path.clone(),
+                    pixel_size,
+                    dither_strength,
+                    bitmap: TriColorBitmap::dithered(&downscaled, params.chromatic, dither_strength)
+                        .upscaled(pixel_size),
+                };
+                let mut bytes: Vec<u8> = Vec::new();
+                data.draw_to_qoi(&mut Cursor::new(&mut bytes))?;
+                if bytes.len() <= SERVER_SIDE_IMAGE_BYTES {
+                    return Ok(Box::new(data));
+                }
+            }
+        }
+        Err(anyhow!(
+            "Photo {} does not fit in {} bytes even at the coarsest dither",
+            path,
+            SERVER_SIDE_IMAGE_BYTES
+        ))
+    }
+
+    fn draw_to_qoi<W>(&self, writer: &mut W) -> Result<()>
+    where
+        W: std::io::Write + std::io::Seek,
+    {
+        let mut display = SimulatorDisplay::<TriColor>::new(Size::new(800, 480));
+        self.draw(&mut display)?;
+        let output_settings = OutputSettingsBuilder::new().scale(1).build();
+        let output_image = display.to_rgb_output_image(&output_settings);
+        let image_buffer = output_image.as_image_buffer();
+        image_buffer.write_to(writer, image::ImageFormat::Qoi)?;
+        Ok(())
+    }
+
+    fn parse_params(params: &HashMap<String, Value>) -> Result<PhotoParams> {
+        let path = params
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or(anyhow!("Missing photo path"))?;
+        let chromatic = params.get("chromatic").and_then(|v| v.as_bool()).unwrap_or(true);
+        Ok(PhotoParams {
+            path: path.into(),
+            chromatic,
+        })
+    }
+
+    fn draw<D, E>(&self, target: &mut D) -> Result<(), E>
+    where
+        E: Debug,
+        D: DrawTarget<Color = TriColor, Error = E>,
+    {
+        draw(target, self)
+    }
+}
+
+/// Returns `path` itself if it is a file, otherwise a random photo from the directory
+fn pick_photo(path: &str) -> Result<String> {
+    if !Path::new(path).is_dir() {
+        return Ok(path.to_string());
+    }
+    let mut photos: Vec<String> = std::fs::read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| PHOTO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        })
+        .filter_map(|p| p.to_str().map(|s| s.to_string()))
+        .collect();
+    photos.sort();
+    photos
+        .choose(&mut rand::rng())
+        .cloned()
+        .ok_or(anyhow!("No photos found in {}", path))
+}
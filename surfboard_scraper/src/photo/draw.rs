@@ -0,0 +1,12 @@
+use crate::photo::data::PhotoData;
+use core::fmt::Debug;
+use embedded_graphics::prelude::*;
+use epd_waveshare::color::TriColor;
+
+pub fn draw<D, E>(target: &mut D, data: &PhotoData) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    data.bitmap.draw(Point::zero(), target)
+}
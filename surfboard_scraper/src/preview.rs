@@ -0,0 +1,184 @@
+use anyhow::{Result, anyhow};
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{Html, IntoResponse, Response},
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use embedded_graphics_simulator::OutputSettingsBuilder;
+use std::{io::Cursor, net::SocketAddr, path::PathBuf, sync::Arc};
+use tracing::info;
+
+use crate::{
+    device_config::{Configuration, ScreenConfiguration},
+    http::DataSource,
+    reload::directories_version,
+    screen::RenderContext,
+};
+
+/// How often the preview pages ask `/version` whether to reload
+const RELOAD_INTERVAL_MS: u32 = 1000;
+
+#[derive(Debug, Clone)]
+pub struct PreviewOptions {
+    pub config_directory: PathBuf,
+    pub address: SocketAddr,
+    // render from saved responses instead of Surfline, the directory is watched like the configs
+    pub fixtures: Option<PathBuf>,
+    pub frozen_time: Option<DateTime<Utc>>,
+}
+
+/// Serve the device configs and their screens as PNGs until the process is stopped
+pub async fn serve(options: PreviewOptions) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(options.address).await?;
+    info!(config_directory = ?options.config_directory, "Previewing on http://{}", options.address);
+    axum::serve(listener, router(options)).await?;
+    Ok(())
+}
+
+pub fn router(options: PreviewOptions) -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/version", get(version))
+        .route("/device/{device}", get(device_page))
+        .route("/device/{device}/screen/{file}", get(screen_png))
+        .with_state(Arc::new(options))
+}
+
+type PreviewState = State<Arc<PreviewOptions>>;
+
+fn error_response(status: StatusCode, error: anyhow::Error) -> Response {
+    (status, format!("{:#}", error)).into_response()
+}
+
+/// Device names are the config file names without `.json`, e.g. `0001`
+fn device_names(options: &PreviewOptions) -> Result<Vec<String>> {
+    let mut names: Vec<String> = std::fs::read_dir(&options.config_directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .filter_map(|path| path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+fn load_device(options: &PreviewOptions, device: &str) -> Result<Configuration> {
+    if !device_names(options)?.iter().any(|name| name == device) {
+        return Err(anyhow!("No device config {}", device));
+    }
+    let path = options.config_directory.join(format!("{}.json", device));
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn render_context(options: &PreviewOptions, config: &Configuration) -> RenderContext {
+    RenderContext {
+        data_source: match &options.fixtures {
+            Some(fixtures) => DataSource::Fixtures(fixtures.clone()),
+            None => DataSource::Live,
+        },
+        frozen_time: options.frozen_time,
+        ..config.render_context()
+    }
+}
+
+/// Changes whenever a config or fixture file is added, removed or modified
+pub fn content_version(options: &PreviewOptions) -> String {
+    directories_version(
+        std::iter::once(options.config_directory.clone())
+            .chain(options.fixtures.clone())
+            .collect(),
+    )
+}
+
+fn page(title: &str, body: &str, version: &str) -> Html<String> {
+    Html(format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<title>{title}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+figure {{ display: inline-block; margin: 0 2em 2em 0; }}
+img {{ border: 1px solid #888; width: 800px; height: 480px; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{body}
+<script>
+const version = "{version}";
+setInterval(async () => {{
+    const response = await fetch("/version").catch(() => null);
+    if (response && response.ok && (await response.text()) !== version) location.reload();
+}}, {RELOAD_INTERVAL_MS});
+</script>
+</body>
+</html>
+"#
+    ))
+}
+
+async fn index(State(options): PreviewState) -> Response {
+    let names = match device_names(&options) {
+        Ok(names) => names,
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+    let links: String = names
+        .iter()
+        .map(|name| format!("<li><a href=\"/device/{0}\">{0}</a></li>\n", name))
+        .collect();
+    page("Devices", &format!("<ul>\n{}</ul>", links), &content_version(&options)).into_response()
+}
+
+async fn version(State(options): PreviewState) -> String {
+    content_version(&options)
+}
+
+async fn device_page(State(options): PreviewState, Path(device): Path<String>) -> Response {
+    let config = match load_device(&options, &device) {
+        Ok(config) => config,
+        Err(e) => return error_response(StatusCode::NOT_FOUND, e),
+    };
+    let version = content_version(&options);
+    // the version in the URL keeps the browser from showing a stale image after a reload
+    let figures: String = config
+        .all_screens()
+        .map(|screen| {
+            format!(
+                "<figure><img src=\"/device/{0}/screen/{1}.png?v={2}\"><figcaption>{1} ({3:?})</figcaption></figure>\n",
+                device, screen.id, version, screen.key
+            )
+        })
+        .collect();
+    page(&format!("Device {}", device), &figures, &version).into_response()
+}
+
+async fn screen_png(State(options): PreviewState, Path((device, file)): Path<(String, String)>) -> Response {
+    let Some(id) = file.strip_suffix(".png") else {
+        return error_response(StatusCode::NOT_FOUND, anyhow!("Screens are only rendered as PNG"));
+    };
+    let config = match load_device(&options, &device) {
+        Ok(config) => config,
+        Err(e) => return error_response(StatusCode::NOT_FOUND, e),
+    };
+    let Some(screen) = config.all_screens().find(|screen| screen.id == id) else {
+        return error_response(StatusCode::NOT_FOUND, anyhow!("No screen {} on device {}", id, device));
+    };
+    match render_png(screen, &render_context(&options, &config)).await {
+        Ok(png) => ([(header::CONTENT_TYPE, "image/png")], png).into_response(),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }
+}
+
+async fn render_png(screen: &ScreenConfiguration, context: &RenderContext) -> Result<Vec<u8>> {
+    let display = screen.draw_to_display(context).await?;
+    let output_settings = OutputSettingsBuilder::new().scale(1).build();
+    let output_image = display.to_rgb_output_image(&output_settings);
+    let mut png: Vec<u8> = Vec::new();
+    output_image
+        .as_image_buffer()
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)?;
+    Ok(png)
+}
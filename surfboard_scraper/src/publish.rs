@@ -0,0 +1,353 @@
+use anyhow::{Context, Result, anyhow};
+use aws_config::{Region, meta::region::RegionProviderChain};
+use aws_sdk_s3::{Client, primitives::ByteStream, types::ObjectCannedAcl};
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf};
+
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// Where a device's screens and config are uploaded, set per device config with `"publish": { "type": ... }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PublishConfig {
+    /// AWS S3, objects are downloaded from `https://<bucket>.s3.<region>.amazonaws.com/<key>`
+    AwsS3 {
+        bucket: String,
+        #[serde(default = "default_region")]
+        region: String,
+        // named profile in ~/.aws, the default credential chain when unset
+        #[serde(default)]
+        profile: Option<String>,
+        #[serde(default = "default_public_read")]
+        public_read: bool,
+    },
+    /// S3-compatible server such as MinIO or Garage, addressed path-style, objects are downloaded from
+    /// `<endpoint>/<bucket>/<key>`
+    S3Compatible {
+        endpoint: String,
+        bucket: String,
+        #[serde(default = "default_region")]
+        region: String,
+        #[serde(default)]
+        profile: Option<String>,
+        // most self-hosted servers reject ACLs and use bucket policies instead
+        #[serde(default)]
+        public_read: bool,
+    },
+    /// Local directory served by a web server at `public_url`
+    Directory { path: PathBuf, public_url: String },
+}
+
+fn default_region() -> String {
+    DEFAULT_REGION.to_string()
+}
+
+fn default_public_read() -> bool {
+    true
+}
+
+impl PublishConfig {
+    /// URL every published object is downloaded from followed by its key, always ending in `/`
+    pub fn public_url(&self) -> String {
+        let url = match self {
+            PublishConfig::AwsS3 { bucket, region, .. } => format!("https://{}.s3.{}.amazonaws.com/", bucket, region),
+            PublishConfig::S3Compatible { endpoint, bucket, .. } => {
+                format!("{}/{}/", endpoint.trim_end_matches('/'), bucket)
+            }
+            PublishConfig::Directory { public_url, .. } => public_url.clone(),
+        };
+        if url.ends_with('/') { url } else { url + "/" }
+    }
+
+    /// Key a screen is published under, its `url` without the target's public URL
+    pub fn key(&self, url: &str) -> Result<String> {
+        let public_url = self.public_url();
+        url.strip_prefix(&public_url)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .ok_or(anyhow!("Screen URL {} is not published under {}", url, public_url))
+    }
+
+    pub async fn connect(&self) -> Publisher {
+        match self {
+            PublishConfig::AwsS3 {
+                bucket,
+                region,
+                profile,
+                public_read,
+            } => Publisher::S3(S3Target::new(None, bucket, region, profile.as_deref(), *public_read).await),
+            PublishConfig::S3Compatible {
+                endpoint,
+                bucket,
+                region,
+                profile,
+                public_read,
+            } => Publisher::S3(S3Target::new(Some(endpoint), bucket, region, profile.as_deref(), *public_read).await),
+            PublishConfig::Directory { path, .. } => Publisher::Directory(DirectoryTarget { path: path.clone() }),
+        }
+    }
+}
+
+/// Hex MD5 of published bytes, the same as the ETag S3 gives an object uploaded in one part
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Md5::digest(bytes))
+}
+
+pub trait PublishTarget {
+    /// Store `bytes` under `key`, replacing what was there
+    fn put(&self, key: &str, bytes: Vec<u8>) -> impl std::future::Future<Output = Result<()>> + Send;
+
+    /// What is stored under `key`, None when nothing is
+    fn get(&self, key: &str) -> impl std::future::Future<Output = Result<Option<Vec<u8>>>> + Send;
+
+    /// `content_hash` of what is stored under `key`, None when nothing is
+    fn stored_hash(&self, key: &str) -> impl std::future::Future<Output = Result<Option<String>>> + Send;
+
+    /// Keys of everything stored under a key starting with `prefix`
+    fn list(&self, prefix: &str) -> impl std::future::Future<Output = Result<Vec<String>>> + Send;
+
+    fn delete(&self, key: &str) -> impl std::future::Future<Output = Result<()>> + Send;
+}
+
+/// Store `bytes` under `key` unless the same bytes are already there, returns whether they were uploaded
+pub async fn put_if_changed<T: PublishTarget>(target: &T, key: &str, bytes: Vec<u8>) -> Result<bool> {
+    if target.stored_hash(key).await? == Some(content_hash(&bytes)) {
+        return Ok(false);
+    }
+    target.put(key, bytes).await?;
+    Ok(true)
+}
+
+/// Publisher of every device config by its key, connected once and again only when the config's `"publish"`
+/// changes, so an S3 client and its credentials aren't rebuilt every cycle
+#[derive(Default)]
+pub struct Connections {
+    publishers: HashMap<String, (PublishConfig, Publisher)>,
+}
+
+impl Connections {
+    /// The device's publisher, connecting when it has none yet or its `publish` changed
+    pub async fn connect(&mut self, config_key: &str, publish: &PublishConfig) -> &Publisher {
+        let connected = self
+            .publishers
+            .get(config_key)
+            .is_some_and(|(config, _)| config == publish);
+        if !connected {
+            let publisher = publish.connect().await;
+            self.publishers
+                .insert(config_key.to_string(), (publish.clone(), publisher));
+        }
+        &self.publishers[config_key].1
+    }
+}
+
+/// The target a device config picked
+pub enum Publisher {
+    S3(S3Target),
+    Directory(DirectoryTarget),
+}
+
+impl PublishTarget for Publisher {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        match self {
+            Publisher::S3(target) => target.put(key, bytes).await,
+            Publisher::Directory(target) => target.put(key, bytes).await,
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self {
+            Publisher::S3(target) => target.get(key).await,
+            Publisher::Directory(target) => target.get(key).await,
+        }
+    }
+
+    async fn stored_hash(&self, key: &str) -> Result<Option<String>> {
+        match self {
+            Publisher::S3(target) => target.stored_hash(key).await,
+            Publisher::Directory(target) => target.stored_hash(key).await,
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        match self {
+            Publisher::S3(target) => target.list(prefix).await,
+            Publisher::Directory(target) => target.list(prefix).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self {
+            Publisher::S3(target) => target.delete(key).await,
+            Publisher::Directory(target) => target.delete(key).await,
+        }
+    }
+}
+
+pub struct S3Target {
+    client: Client,
+    bucket: String,
+    public_read: bool,
+}
+
+impl S3Target {
+    /// Client for AWS S3, or with an `endpoint` for an S3-compatible server
+    pub async fn new(
+        endpoint: Option<&str>,
+        bucket: &str,
+        region: &str,
+        profile: Option<&str>,
+        public_read: bool,
+    ) -> Self {
+        let region_provider = RegionProviderChain::first_try(Region::new(region.to_string()));
+        let mut loader = aws_config::from_env().region(region_provider);
+        if let Some(profile) = profile {
+            loader = loader.profile_name(profile);
+        }
+        if let Some(endpoint) = endpoint {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let sdk_config = loader.load().await;
+        // self-hosted servers rarely have a DNS name per bucket
+        let s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
+            .force_path_style(endpoint.is_some())
+            .build();
+        S3Target {
+            client: Client::from_conf(s3_config),
+            bucket: bucket.to_string(),
+            public_read,
+        }
+    }
+}
+
+impl PublishTarget for S3Target {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let mut request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(bytes));
+        if self.public_read {
+            request = request.acl(ObjectCannedAcl::PublicRead);
+        }
+        request
+            .send()
+            .await
+            .with_context(|| format!("Failed to upload {} to bucket {}", key, self.bucket))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let response = self.client.get_object().bucket(&self.bucket).key(key).send().await;
+        let object = match response {
+            Ok(object) => object,
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_no_such_key()) => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to download {} from bucket {}", key, self.bucket)),
+        };
+        let bytes = object
+            .body
+            .collect()
+            .await
+            .with_context(|| format!("Failed to download {} from bucket {}", key, self.bucket))?;
+        Ok(Some(bytes.into_bytes().to_vec()))
+    }
+
+    async fn stored_hash(&self, key: &str) -> Result<Option<String>> {
+        let response = self.client.head_object().bucket(&self.bucket).key(key).send().await;
+        match response {
+            Ok(object) => Ok(object.e_tag().map(|etag| etag.trim_matches('"').to_string())),
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found()) => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to look up {} in bucket {}", key, self.bucket)),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(prefix)
+            .into_paginator()
+            .send();
+        while let Some(page) = pages.next().await {
+            let page = page.with_context(|| format!("Failed to list {} in bucket {}", prefix, self.bucket))?;
+            keys.extend(page.contents().iter().filter_map(|object| object.key()).map(str::to_string));
+        }
+        Ok(keys)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("Failed to delete {} from bucket {}", key, self.bucket))?;
+        Ok(())
+    }
+}
+
+pub struct DirectoryTarget {
+    pub path: PathBuf,
+}
+
+impl PublishTarget for DirectoryTarget {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let path = self.path.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // write next to the file and rename so a web server never serves half an image
+        let mut partial_path = path.clone().into_os_string();
+        partial_path.push(".partial");
+        tokio::fs::write(&partial_path, bytes)
+            .await
+            .with_context(|| format!("Failed to write {:?}", partial_path))?;
+        tokio::fs::rename(&partial_path, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path.join(key)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {:?}", self.path.join(key))),
+        }
+    }
+
+    async fn stored_hash(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.get(key).await?.map(|bytes| content_hash(&bytes)))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let (directory, name) = prefix.rsplit_once('/').unwrap_or(("", prefix));
+        let mut keys = Vec::new();
+        let mut entries = match tokio::fs::read_dir(self.path.join(directory)).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(keys),
+            Err(e) => return Err(e).with_context(|| format!("Failed to list {:?}", self.path.join(directory))),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            if let Some(file_name) = entry.file_name().to_str()
+                && file_name.starts_with(name)
+                && entry.file_type().await?.is_file()
+            {
+                keys.push(match directory {
+                    "" => file_name.to_string(),
+                    _ => format!("{}/{}", directory, file_name),
+                });
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        tokio::fs::remove_file(self.path.join(key))
+            .await
+            .with_context(|| format!("Failed to delete {:?}", self.path.join(key)))
+    }
+}
@@ -0,0 +1,166 @@
+use chrono::{DateTime, NaiveTime, TimeDelta, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::device_config::ScreenConfiguration;
+
+/// How often the scheduler checks for screens that are due
+pub const TICK: TimeDelta = TimeDelta::minutes(5);
+
+/// A screen that failed is tried again after this long at the latest
+pub const RETRY_AFTER: TimeDelta = TimeDelta::minutes(30);
+
+// how far ahead `Scheduler::next_run` looks for the next due tick
+const LOOKAHEAD: TimeDelta = TimeDelta::hours(48);
+
+/// When a screen is rendered again, set per screen with `"refresh"`. Every screen also renders when its
+/// configuration changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshPolicy {
+    /// `{ "interval": { "every_minutes": 180, "windows": [...], "quiet_hours": [...] } }`
+    Interval(RefreshInterval),
+    /// `"on_change"`, only when the configuration changes
+    OnChange,
+}
+
+/// Re-render every `every_minutes`, or at the cadence of the window the local time falls in, never in quiet hours
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshInterval {
+    #[serde(default = "default_every_minutes")]
+    pub every_minutes: u32,
+    // e.g. `{ "from": "05:00", "to": "12:00", "every_minutes": 30 }` for mornings, first match wins
+    #[serde(default)]
+    pub windows: Vec<RefreshWindow>,
+    // e.g. `{ "from": "22:00", "to": "05:00" }`
+    #[serde(default)]
+    pub quiet_hours: Vec<TimeWindow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshWindow {
+    #[serde(flatten)]
+    pub window: TimeWindow,
+    pub every_minutes: u32,
+}
+
+/// Local time of day from `from` until `to`, wrapping past midnight when `to` is earlier
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub from: NaiveTime,
+    pub to: NaiveTime,
+}
+
+/// The 3 hours the scraper always slept between cycles
+fn default_every_minutes() -> u32 {
+    180
+}
+
+impl Default for RefreshInterval {
+    fn default() -> Self {
+        RefreshInterval {
+            every_minutes: default_every_minutes(),
+            windows: Vec::new(),
+            quiet_hours: Vec::new(),
+        }
+    }
+}
+
+impl TimeWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.from <= self.to {
+            self.from <= time && time < self.to
+        } else {
+            time >= self.from || time < self.to
+        }
+    }
+}
+
+impl RefreshInterval {
+    /// Cadence at a local time of day, None in quiet hours
+    pub fn cadence(&self, time: NaiveTime) -> Option<TimeDelta> {
+        if self.quiet_hours.iter().any(|quiet| quiet.contains(time)) {
+            return None;
+        }
+        let every_minutes = self
+            .windows
+            .iter()
+            .find(|window| window.window.contains(time))
+            .map_or(self.every_minutes, |window| window.every_minutes);
+        Some(TimeDelta::minutes(every_minutes.into()))
+    }
+}
+
+struct Job {
+    // the configuration the screen last rendered with, serialized
+    config: String,
+    last_run: DateTime<Utc>,
+    succeeded: bool,
+}
+
+/// Refresh jobs by output URL, screens that publish to the same URL share a job
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: HashMap<String, Job>,
+}
+
+impl Scheduler {
+    /// Whether the screen should render at `now`, the time zone is the one refresh windows are in
+    pub fn is_due<Tz: TimeZone>(&self, screen: &ScreenConfiguration, now: &DateTime<Tz>) -> bool {
+        self.is_due_with(&screen.url, &serialize(screen), &screen.refresh_policy(), now)
+    }
+
+    /// `is_due` for a screen already serialized and with its policy resolved, only the time is left to check
+    fn is_due_with<Tz: TimeZone>(&self, url: &str, config: &str, policy: &RefreshPolicy, now: &DateTime<Tz>) -> bool {
+        let Some(job) = self.jobs.get(url) else {
+            return true;
+        };
+        if job.config != config {
+            return true;
+        }
+        let since = now.with_timezone(&Utc) - job.last_run;
+        let cadence = match policy {
+            RefreshPolicy::Interval(interval) => match interval.cadence(now.time()) {
+                Some(cadence) => cadence,
+                None => return false,
+            },
+            RefreshPolicy::OnChange => TimeDelta::MAX,
+        };
+        if job.succeeded {
+            since >= cadence
+        } else {
+            since >= cadence.min(RETRY_AFTER)
+        }
+    }
+
+    /// Record that the screen rendered, or failed to, at `now`
+    pub fn ran<Tz: TimeZone>(&mut self, screen: &ScreenConfiguration, now: &DateTime<Tz>, succeeded: bool) {
+        self.jobs.insert(
+            screen.url.clone(),
+            Job {
+                config: serialize(screen),
+                last_run: now.with_timezone(&Utc),
+                succeeded,
+            },
+        );
+    }
+
+    /// First tick after `now` the screen is due at if its configuration stays the same, None when only a change
+    /// renders it again
+    pub fn next_run<Tz: TimeZone>(&self, screen: &ScreenConfiguration, now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+        let config = serialize(screen);
+        let policy = screen.refresh_policy();
+        let ticks = LOOKAHEAD.num_minutes() / TICK.num_minutes();
+        (1..=ticks)
+            .map(|tick| now.clone() + TICK * tick as i32)
+            .find(|time| self.is_due_with(&screen.url, &config, &policy, time))
+            .map(|time| time.with_timezone(&Utc))
+    }
+}
+
+/// Everything about the screen a change of should render it again, the server-side fields included. A `Value` keeps
+/// `params` in key order, whichever order the HashMap of a reloaded config iterates in
+fn serialize(screen: &ScreenConfiguration) -> String {
+    let device_facing = serde_json::to_value(screen).unwrap_or_default();
+    format!("{} {:?} {}", device_facing, screen.refresh, screen.stale_after_hours)
+}
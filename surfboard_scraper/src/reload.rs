@@ -0,0 +1,113 @@
+use anyhow::Result;
+use chrono::TimeDelta;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    task::{Context, Waker},
+    time::Duration,
+};
+use tokio::{
+    signal::unix::{Signal, SignalKind, signal},
+    time::{interval, sleep},
+};
+
+/// How often the config directory is checked for changes between ticks
+pub const CONFIG_POLL: Duration = Duration::from_secs(10);
+
+/// Changes whenever a file in one of the directories or their subdirectories is added, removed or modified
+pub fn directories_version(directories: Vec<PathBuf>) -> String {
+    let mut hasher = DefaultHasher::new();
+    let mut directories = directories;
+    while let Some(directory) = directories.pop() {
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect();
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                directories.push(path);
+            } else if let Ok(metadata) = path.metadata() {
+                (&path, metadata.len(), metadata.modified().ok()).hash(&mut hasher);
+            }
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Why the publishing loop stopped waiting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Wakeup {
+    /// The tick is over
+    Tick,
+    /// SIGHUP, or a device config changed
+    Reload,
+    /// SIGTERM or Ctrl-C
+    Shutdown,
+}
+
+/// Signals and config changes that wake the publishing loop before its next tick
+pub struct Wakeups {
+    config_directory: PathBuf,
+    // `directories_version` of the configs when they were last checked
+    version: String,
+    terminate: Signal,
+    interrupt: Signal,
+    hangup: Signal,
+    // sticky once a shutdown signal has been seen
+    shutdown: bool,
+}
+
+impl Wakeups {
+    /// Take over SIGTERM, SIGINT and SIGHUP and watch `config_directory`
+    pub fn new(config_directory: impl Into<PathBuf>) -> Result<Self> {
+        let config_directory = config_directory.into();
+        Ok(Wakeups {
+            version: directories_version(vec![config_directory.clone()]),
+            config_directory,
+            terminate: signal(SignalKind::terminate())?,
+            interrupt: signal(SignalKind::interrupt())?,
+            hangup: signal(SignalKind::hangup())?,
+            shutdown: false,
+        })
+    }
+
+    /// Whether a shutdown signal has arrived, without waiting for one
+    pub fn shutdown_requested(&mut self) -> bool {
+        let mut context = Context::from_waker(Waker::noop());
+        self.shutdown = self.shutdown
+            || self.terminate.poll_recv(&mut context).is_ready()
+            || self.interrupt.poll_recv(&mut context).is_ready();
+        self.shutdown
+    }
+
+    /// Wait for the end of the tick, a reload or a shutdown, whichever comes first
+    pub async fn wait(&mut self, tick: TimeDelta) -> Result<Wakeup> {
+        if self.shutdown_requested() {
+            return Ok(Wakeup::Shutdown);
+        }
+        let deadline = sleep(tick.to_std()?);
+        tokio::pin!(deadline);
+        let mut poll = interval(CONFIG_POLL);
+        loop {
+            tokio::select! {
+                _ = &mut deadline => return Ok(Wakeup::Tick),
+                _ = self.terminate.recv() => break,
+                _ = self.interrupt.recv() => break,
+                _ = self.hangup.recv() => return Ok(Wakeup::Reload),
+                _ = poll.tick() => {
+                    let version = directories_version(vec![self.config_directory.clone()]);
+                    if version != self.version {
+                        self.version = version;
+                        return Ok(Wakeup::Reload);
+                    }
+                }
+            }
+        }
+        self.shutdown = true;
+        Ok(Wakeup::Shutdown)
+    }
+}
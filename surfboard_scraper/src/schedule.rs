@@ -0,0 +1,44 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Recurring window in which a screen is shown
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Recurrence {
+    /// Every year on the given day, e.g. a birthday
+    Yearly { month: u32, day: u32 },
+    /// Every week on the given weekdays, e.g. `["Sat", "Sun"]`
+    Weekly(Vec<Weekday>),
+}
+
+impl Recurrence {
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        match self {
+            Recurrence::Yearly { month, day } => date.month() == *month && date.day() == *day,
+            Recurrence::Weekly(weekdays) => weekdays.contains(&date.weekday()),
+        }
+    }
+}
+
+/// Dates on which a screen is active. A screen with no schedule is always active.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ScreenSchedule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_from: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_until: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurring: Option<Recurrence>,
+}
+
+impl ScreenSchedule {
+    /// Whether the screen should be shown on `date`, both bounds are inclusive
+    pub fn is_active(&self, date: NaiveDate) -> bool {
+        self.active_from.is_none_or(|from| date >= from)
+            && self.active_until.is_none_or(|until| date <= until)
+            && self
+                .recurring
+                .as_ref()
+                .is_none_or(|recurrence| recurrence.matches(date))
+    }
+}
@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+
+use crate::astronomy::DayAstronomy;
+use crate::common::draw_utils::get_local_time_from_unix;
+use crate::surfline_types::{
+    tide::TideMeasurement,
+    wave::WaveMeasurement,
+    wind::{WindDirectionType, WindMeasurement},
+};
+
+/// Hours around a peak stay in its window while they score at least this fraction of the peak
+const WINDOW_THRESHOLD: f32 = 0.75;
+const MAX_WINDOW_SECONDS: i64 = 4 * 3600;
+/// Wind below this is glassy whatever its direction
+const GLASSY_WIND_SPEED: f32 = 3.;
+/// Tide this many feet outside the ideal range scores zero
+const TIDE_FALLOFF: f32 = 2.;
+
+/// What makes a session worth paddling out for at a spot, set with the `preferences` param
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct SessionPreferences {
+    /// Smallest wave face in feet, smaller waves score zero
+    pub min_wave_height: i32,
+    /// Strongest cross-shore or onshore wind in knots, offshore wind is tolerated up to half again as much
+    pub max_wind_speed: f32,
+    /// Tide heights in feet that work best, e.g. `[1.0, 4.0]`
+    pub ideal_tide: Option<(f32, f32)>,
+}
+
+impl Default for SessionPreferences {
+    fn default() -> Self {
+        SessionPreferences {
+            min_wave_height: 2,
+            max_wind_speed: 12.,
+            ideal_tide: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HourScore {
+    pub timestamp: i64,
+    pub utc_offset: i32,
+    /// 0 is not surfable, 1 ticks every box
+    pub score: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SessionWindow {
+    pub start: i64,
+    pub end: i64,
+    pub utc_offset: i32,
+    pub score: f32,
+}
+
+impl SessionPreferences {
+    fn wave_score(&self, wave: &WaveMeasurement) -> f32 {
+        if wave.surf.max < self.min_wave_height {
+            return 0.;
+        }
+        // bigger is better, up to twice the minimum
+        let mean = (wave.surf.min + wave.surf.max) as f32 / 2.;
+        let min = self.min_wave_height.max(1) as f32;
+        (0.5 + 0.5 * (mean - min) / min).clamp(0.5, 1.)
+    }
+
+    fn wind_score(&self, wind: &WindMeasurement) -> f32 {
+        if wind.speed <= GLASSY_WIND_SPEED {
+            return 1.;
+        }
+        let max_speed = match wind.direction_type {
+            WindDirectionType::Offshore => self.max_wind_speed * 1.5,
+            WindDirectionType::CrossShore | WindDirectionType::Onshore => self.max_wind_speed,
+        };
+        let score = (1. - (wind.speed - GLASSY_WIND_SPEED) / (max_speed - GLASSY_WIND_SPEED).max(1.)).clamp(0., 1.);
+        if wind.direction_type == WindDirectionType::Onshore {
+            score * 0.7
+        } else {
+            score
+        }
+    }
+
+    fn tide_score(&self, tide: &TideMeasurement) -> f32 {
+        match self.ideal_tide {
+            Some((low, high)) => {
+                let distance = (low - tide.height).max(tide.height - high).max(0.);
+                (1. - distance / TIDE_FALLOFF).max(0.)
+            }
+            None => 1.,
+        }
+    }
+}
+
+/// Measurement closest in time to `timestamp`
+fn nearest<T>(measurements: &[T], timestamp: i64, timestamp_of: fn(&T) -> i64) -> Option<&T> {
+    measurements
+        .iter()
+        .min_by_key(|measurement| (timestamp_of(measurement) - timestamp).abs())
+}
+
+/// Rate every wave forecast hour by combining size, wind and tide, a deal-breaker in any of them zeroes the hour
+pub fn score_hours(
+    waves: &[WaveMeasurement],
+    wind: &[WindMeasurement],
+    tides: &[TideMeasurement],
+    preferences: &SessionPreferences,
+) -> Vec<HourScore> {
+    waves
+        .iter()
+        .map(|wave| {
+            let wind = nearest(wind, wave.timestamp, |w| w.timestamp);
+            let tide = nearest(tides, wave.timestamp, |t| t.timestamp);
+            let score = preferences.wave_score(wave)
+                * wind.map(|w| preferences.wind_score(w)).unwrap_or(1.)
+                * tide.map(|t| preferences.tide_score(t)).unwrap_or(1.);
+            HourScore {
+                timestamp: wave.timestamp,
+                utc_offset: wind.map(|w| w.utc_offset).or(tide.map(|t| t.utc_offset)).unwrap_or(0),
+                score,
+            }
+        })
+        .collect()
+}
+
+/// Up to `count` non-overlapping windows, best first. Each grows from the highest remaining hour towards
+/// its better neighbour while the neighbours keep up with the peak, for at most four hours.
+pub fn best_windows(scores: &[HourScore], count: usize) -> Vec<SessionWindow> {
+    let interval = scores
+        .windows(2)
+        .map(|pair| pair[1].timestamp - pair[0].timestamp)
+        .min()
+        .unwrap_or(3600);
+    let mut taken = vec![false; scores.len()];
+    let mut windows = Vec::new();
+
+    while windows.len() < count {
+        let Some(peak) = (0..scores.len())
+            .filter(|&i| !taken[i] && scores[i].score > 0.)
+            .max_by(|&a, &b| scores[a].score.total_cmp(&scores[b].score))
+        else {
+            break;
+        };
+        let threshold = scores[peak].score * WINDOW_THRESHOLD;
+        let joins = |i: usize, neighbour: usize| {
+            !taken[neighbour]
+                && scores[neighbour].score >= threshold
+                && (scores[neighbour].timestamp - scores[i].timestamp).abs() == interval
+        };
+
+        let (mut start, mut end) = (peak, peak);
+        while scores[end].timestamp - scores[start].timestamp + 2 * interval <= MAX_WINDOW_SECONDS {
+            let before = (start > 0 && joins(start, start - 1)).then_some(start - 1);
+            let after = (end + 1 < scores.len() && joins(end, end + 1)).then_some(end + 1);
+            match (before, after) {
+                (Some(b), Some(a)) if scores[b].score > scores[a].score => start = b,
+                (_, Some(a)) => end = a,
+                (Some(b), None) => start = b,
+                (None, None) => break,
+            }
+        }
+
+        // keep a gap so the next window doesn't just continue this one
+        taken[start.saturating_sub(1)..=(end + 1).min(scores.len() - 1)].fill(true);
+        let hours = &scores[start..=end];
+        windows.push(SessionWindow {
+            start: scores[start].timestamp,
+            end: scores[end].timestamp + interval,
+            utc_offset: scores[peak].utc_offset,
+            score: hours.iter().map(|hour| hour.score).sum::<f32>() / hours.len() as f32,
+        });
+    }
+    windows
+}
+
+/// Zero the hours between civil dusk and dawn, nobody wants a 3am session
+pub fn daylight_only(scores: &[HourScore], astronomy: &[DayAstronomy]) -> Vec<HourScore> {
+    scores
+        .iter()
+        .map(|hour| {
+            let date = get_local_time_from_unix(hour.timestamp, hour.utc_offset).date();
+            let is_light = astronomy.iter().find(|day| day.date == date).is_none_or(|day| {
+                day.sun.civil_dawn.is_none_or(|dawn| hour.timestamp >= dawn.timestamp())
+                    && day.sun.civil_dusk.is_none_or(|dusk| hour.timestamp <= dusk.timestamp())
+            });
+            HourScore {
+                score: if is_light { hour.score } else { 0. },
+                ..hour.clone()
+            }
+        })
+        .collect()
+}
+
+/// The best window of every local day that has one, in date order
+pub fn best_window_per_day(scores: &[HourScore]) -> Vec<SessionWindow> {
+    scores
+        .chunk_by(|a, b| {
+            get_local_time_from_unix(a.timestamp, a.utc_offset).date()
+                == get_local_time_from_unix(b.timestamp, b.utc_offset).date()
+        })
+        .flat_map(|day| best_windows(day, 1))
+        .collect()
+}
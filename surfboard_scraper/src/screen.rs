@@ -2,21 +2,62 @@ use core::fmt::Debug;
 use std::collections::HashMap;
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use embedded_graphics::prelude::DrawTarget;
 use epd_waveshare::color::TriColor;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::{http::DataSource, locale::Locale};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ScreenIdentifier {
     SurfReport24h,
     SurfReportWeek,
     Message,
+    Photo,
+}
+
+/// Encoding of the image uploaded for a screen
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// RGB QOI image, decoded pixel by pixel on the device
+    #[default]
+    Qoi,
+    /// PackBits-compressed epd7in5_v2 framebuffer, see `common::framebuffer`
+    Epd1,
+    /// Same as `Epd1` followed by a second plane for the chromatic pixels
+    Epd2,
+}
+
+impl OutputFormat {
+    pub fn is_qoi(&self) -> bool {
+        *self == OutputFormat::Qoi
+    }
 }
 
+/// Device-wide settings every screen is rendered with, see `Configuration::render_context`
+#[derive(Debug, Clone, Default)]
+pub struct RenderContext {
+    pub locale: Locale,
+    pub data_source: DataSource,
+    // frozen clock for reproducible renders, the system time when unset
+    pub frozen_time: Option<DateTime<Utc>>,
+}
+
+impl RenderContext {
+    pub fn now(&self) -> DateTime<Utc> {
+        self.frozen_time.unwrap_or_else(Utc::now)
+    }
+}
+
+/// Size of the firmware's image download buffers, see `surfboard/src/task/state.rs`
+pub const SERVER_SIDE_IMAGE_BYTES: usize = 1024 * 25;
+
 pub trait Screen<P> {
     fn parse_params(params: &HashMap<String, Value>) -> Result<P>;
-    fn from_params(params: &P) -> impl std::future::Future<Output = Result<Box<Self>>> + Send;
+    fn from_params(params: &P, context: &RenderContext) -> impl std::future::Future<Output = Result<Box<Self>>> + Send;
     fn draw<D, E>(&self, target: &mut D) -> Result<(), E>
     where
         E: Debug,
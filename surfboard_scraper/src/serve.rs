@@ -0,0 +1,95 @@
+use anyhow::Result;
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use std::{
+    net::SocketAddr,
+    path::{Component, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+use crate::publish::content_hash;
+
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    // the `path` of the devices' `"directory"` publish targets
+    pub directory: PathBuf,
+    pub address: SocketAddr,
+}
+
+/// Serve the published screens and device configs to the devices until the process is stopped
+pub async fn serve(options: ServeOptions) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(options.address).await?;
+    info!(directory = ?options.directory, "Serving on http://{}", options.address);
+    axum::serve(listener, router(options.directory)).await?;
+    Ok(())
+}
+
+/// Files under `directory` at their relative path, `GET` and `HEAD` with `ETag` revalidation
+pub fn router(directory: PathBuf) -> Router {
+    Router::new()
+        .route("/{*key}", get(published_file))
+        .with_state(Arc::new(directory))
+}
+
+/// Quoted `content_hash`, the same tag S3 would give the file
+pub fn etag(bytes: &[u8]) -> String {
+    format!("\"{}\"", content_hash(bytes))
+}
+
+fn content_type(key: &str) -> &'static str {
+    match key.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("json") => "application/json",
+        Some("png") => "image/png",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Whether the device's `If-None-Match` already names the current version
+fn is_not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == etag || tag == "*")
+}
+
+async fn published_file(
+    State(directory): State<Arc<PathBuf>>,
+    Path(key): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    // keys are relative paths inside the directory
+    let relative = std::path::Path::new(&key);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let Ok(bytes) = tokio::fs::read(directory.join(relative)).await else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let etag = etag(&bytes);
+    let etag_header = HeaderValue::from_str(&etag).unwrap();
+    if is_not_modified(&headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag_header)]).into_response();
+    }
+    (
+        [
+            (header::ETAG, etag_header),
+            (header::CONTENT_TYPE, HeaderValue::from_static(content_type(&key))),
+            (header::CONTENT_LENGTH, HeaderValue::from(bytes.len())),
+            (header::CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+        ],
+        bytes,
+    )
+        .into_response()
+}
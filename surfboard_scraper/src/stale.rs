@@ -0,0 +1,166 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        iso_8859_1::{FONT_6X10, FONT_10X20},
+    },
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::Text,
+};
+use embedded_graphics_simulator::SimulatorDisplay;
+use epd_waveshare::color::TriColor;
+use std::collections::HashMap;
+use tracing::warn;
+
+use crate::{
+    common::{draw_utils::left_text_style, framebuffer::decode_display},
+    device_config::ScreenConfiguration,
+    locale::Messages,
+    manifest::ScreenManifest,
+    metrics::METRICS,
+    publish::{PublishConfig, PublishTarget},
+    screen::RenderContext,
+};
+
+const BANNER_HEIGHT: u32 = 48;
+
+// FONT_6X10 across the panel less the margins
+const MAX_REASON_CHARS: usize = 130;
+
+struct ScreenHistory {
+    // None until the screen renders for the first time
+    last_good: Option<SimulatorDisplay<TriColor>>,
+    // time of the last good rendering, or of the first failure when there hasn't been one
+    since: DateTime<Utc>,
+}
+
+/// An encoded screen, `stale` when it's the last good rendering under a banner rather than a new one
+#[derive(Debug)]
+pub struct Rendered {
+    pub bytes: Vec<u8>,
+    pub stale: bool,
+}
+
+/// Last good rendering of every published screen by URL, so a screen that keeps failing is replaced with a
+/// stale copy instead of leaving an old image with a misleading "Updated" time on S3
+#[derive(Default)]
+pub struct StaleScreens {
+    screens: HashMap<String, ScreenHistory>,
+}
+
+impl StaleScreens {
+    /// Render a screen in its output format. When it fails to draw or encode, e.g. grows past the device's buffer,
+    /// the error is returned until the screen has been failing for `stale_after_hours`, after that its last good
+    /// rendering is returned under a stale banner
+    pub async fn render(&mut self, screen: &ScreenConfiguration, context: &RenderContext) -> Result<Rendered> {
+        let now = context.now();
+        let rendered = match screen.draw_to_display(context).await {
+            Ok(display) => screen.encode(&display).map(|bytes| (display, bytes)),
+            Err(e) => Err(e),
+        };
+        let error = match rendered {
+            Ok((display, bytes)) => {
+                self.screens.insert(
+                    screen.url.clone(),
+                    ScreenHistory {
+                        last_good: Some(display),
+                        since: now,
+                    },
+                );
+                return Ok(Rendered { bytes, stale: false });
+            }
+            Err(e) => e,
+        };
+        METRICS.render_failures.with_label_values(&[&screen.url]).inc();
+        let history = self.screens.entry(screen.url.clone()).or_insert(ScreenHistory {
+            last_good: None,
+            since: now,
+        });
+        if now - history.since < TimeDelta::hours(screen.stale_after_hours.into()) {
+            return Err(error);
+        }
+        warn!(url = screen.url, "Publishing as stale: {:#}", error);
+        let mut display = history
+            .last_good
+            .clone()
+            .unwrap_or_else(|| SimulatorDisplay::new(Size::new(800, 480)));
+        draw_stale_banner(&mut display, context.locale.messages(), history.since, &error)?;
+        Ok(Rendered {
+            bytes: screen.encode(&display)?,
+            stale: true,
+        })
+    }
+
+    /// Pick up the image `entry` points at as the last good rendering of `screen`, for a scraper that restarted or
+    /// runs with `--once` and has no history of its own. A stale image keeps its banner until it's drawn over
+    pub async fn restore<T: PublishTarget>(
+        &mut self,
+        screen: &ScreenConfiguration,
+        entry: &ScreenManifest,
+        publish: &PublishConfig,
+        target: &T,
+    ) -> Result<()> {
+        if self.screens.contains_key(&screen.url) {
+            return Ok(());
+        }
+        let Some(bytes) = target.get(&publish.key(&entry.url)?).await? else {
+            return Ok(());
+        };
+        self.screens.insert(
+            screen.url.clone(),
+            ScreenHistory {
+                last_good: Some(decode_display(&bytes)?),
+                since: entry.valid_from,
+            },
+        );
+        Ok(())
+    }
+
+    /// Time of the last good rendering of the screen at `url`, or of its first failure when it hasn't rendered
+    pub fn fresh_since(&self, url: &str) -> Option<DateTime<Utc>> {
+        self.screens.get(url).map(|history| history.since)
+    }
+}
+
+/// Chromatic band across the top with the time of the last good rendering and why the screen fails
+pub fn draw_stale_banner<D, E>(
+    target: &mut D,
+    messages: &Messages,
+    since: DateTime<Utc>,
+    reason: &anyhow::Error,
+) -> Result<(), E>
+where
+    E: core::fmt::Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    Rectangle::new(
+        Point::zero(),
+        Size::new(target.bounding_box().size.width, BANNER_HEIGHT),
+    )
+    .into_styled(PrimitiveStyle::with_fill(TriColor::Chromatic))
+    .draw(target)?;
+    Text::with_text_style(
+        messages
+            .format_stale_since(&since.with_timezone(&Local).naive_local())
+            .as_str(),
+        Point::new(10, 20),
+        MonoTextStyle::new(&FONT_10X20, TriColor::White),
+        left_text_style(),
+    )
+    .draw(target)?;
+    let reason: String = format!("{:#}", reason)
+        .replace('\n', " ")
+        .chars()
+        .take(MAX_REASON_CHARS)
+        .collect();
+    Text::with_text_style(
+        reason.as_str(),
+        Point::new(10, 40),
+        MonoTextStyle::new(&FONT_6X10, TriColor::White),
+        left_text_style(),
+    )
+    .draw(target)?;
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use chrono::prelude::*;
 use core::fmt::Debug;
 use embedded_graphics::prelude::*;
@@ -15,45 +15,104 @@ const MEASUREMENTS_WIND: usize = 10;
 const MEASUREMENTS_WEATHER: usize = 10;
 
 use crate::{
-    screen::Screen,
+    astronomy::DayAstronomy,
+    common::draw_utils::get_local_time_from_unix,
+    locale::Locale,
+    screen::{RenderContext, Screen},
+    scoring::{SessionPreferences, SessionWindow, best_windows, daylight_only, score_hours},
     surf_report_24h::draw::draw,
     surfline_types::{
+        common::{optional_section, spot_utc_offset},
         conditions::{ConditionsMeasurement, ConditionsResult, fetch_conditions},
-        spot_details::{SpotDetails, SpotDetailsResult, fetch_spot_details},
+        report::{ReportResult, WaterTemperature, fetch_report},
+        spot_details::{SpotDetails, SpotDetailsResult, fetch_spot_details, surfline_spot_url},
         tide::{TideMeasurement, TideResult, fetch_tides},
         wave::{WaveMeasurement, WaveResult, fetch_waves},
         weather::{WeatherMeasurement, WeatherResult, fetch_weather},
         wind::{WindMeasurement, WindResult, fetch_wind},
     },
+    wetsuit::WetsuitTable,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SurfReport24HDataParams {
     spot_id: String,
+    qr_code: bool,
+    wetsuits: WetsuitTable,
+    preferences: SessionPreferences,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SurfReport24HData {
     pub last_updated_utc: i64,
-    pub waves: Vec<WaveMeasurement>,
-    pub tides: Vec<TideMeasurement>,
-    pub weather: Vec<WeatherMeasurement>,
-    pub wind: Vec<WindMeasurement>,
-    pub conditions: ConditionsMeasurement,
-    pub spot_details: SpotDetails,
+    // each section is None when its fetch failed and is drawn as "no data"
+    pub waves: Option<Vec<WaveMeasurement>>,
+    pub tides: Option<Vec<TideMeasurement>>,
+    pub weather: Option<Vec<WeatherMeasurement>>,
+    pub wind: Option<Vec<WindMeasurement>>,
+    pub conditions: Option<ConditionsMeasurement>,
+    pub spot_details: Option<SpotDetails>,
+    pub water_temperature: Option<WaterTemperature>,
+    pub wetsuit: Option<String>,
+    #[serde(default)]
+    pub locale: Locale,
+    // today and tomorrow, the chart spans both
+    pub astronomy: Vec<DayAstronomy>,
+    // top two windows within the tide chart, best first
+    pub best_sessions: Vec<SessionWindow>,
+    // link to the spot's Surfline page, drawn as a QR code in the top right corner
+    pub qr_code: Option<String>,
 }
 
 impl Screen<SurfReport24HDataParams> for SurfReport24HData {
-    async fn from_params(params: &SurfReport24HDataParams) -> Result<Box<Self>> {
+    async fn from_params(params: &SurfReport24HDataParams, context: &RenderContext) -> Result<Box<Self>> {
         let spot_id = params.spot_id.as_str();
-        Ok(Box::new(SurfReport24HData::new_from_results(
-            fetch_waves(spot_id, None).await?,
-            fetch_tides(spot_id, None).await?,
-            fetch_weather(spot_id, None).await?,
-            fetch_wind(spot_id, None).await?,
-            fetch_conditions(spot_id, None).await?,
-            fetch_spot_details(spot_id).await?,
-        )))
+        let source = &context.data_source;
+        let waves = optional_section(spot_id, "waves", fetch_waves(source, spot_id, None).await);
+        let tides = optional_section(spot_id, "tides", fetch_tides(source, spot_id, None).await);
+        let wind = optional_section(spot_id, "wind", fetch_wind(source, spot_id, None).await);
+        // score the hourly forecast before it is thinned out for drawing
+        let scores = match &waves {
+            Some(waves) => score_hours(
+                &waves.data.wave,
+                wind.as_ref().map_or(&[][..], |wind| &wind.data.wind),
+                tides.as_ref().map_or(&[][..], |tides| &tides.data.tides),
+                &params.preferences,
+            ),
+            None => Vec::new(),
+        };
+        let mut data = SurfReport24HData::new_from_results(
+            waves,
+            tides,
+            optional_section(spot_id, "weather", fetch_weather(source, spot_id, None).await),
+            wind,
+            optional_section(spot_id, "conditions", fetch_conditions(source, spot_id, None).await),
+            optional_section(spot_id, "spot details", fetch_spot_details(source, spot_id).await),
+            optional_section(spot_id, "report", fetch_report(source, spot_id).await),
+            context.now(),
+        );
+        if !data.has_forecast() {
+            return Err(anyhow!("No forecast data for spot {}", spot_id));
+        }
+        data.locale = context.locale;
+        data.wetsuit = data
+            .water_temperature
+            .as_ref()
+            .and_then(|water_temperature| params.wetsuits.recommend(water_temperature.average_fahrenheit()))
+            .map(|suit| suit.to_string());
+        let (chart_start, chart_end) = data.chart_span();
+        let chart_scores: Vec<_> = daylight_only(&scores, &data.astronomy)
+            .into_iter()
+            .filter(|hour| hour.timestamp >= chart_start && hour.timestamp < chart_end)
+            .collect();
+        data.best_sessions = best_windows(&chart_scores, 2);
+        if params.qr_code {
+            data.qr_code = data
+                .spot_details
+                .as_ref()
+                .map(|spot| surfline_spot_url(spot_id, &spot.name));
+        }
+        Ok(Box::new(data))
     }
 
     fn draw_to_qoi<W>(&self, writer: &mut W) -> Result<()>
@@ -65,14 +124,29 @@ impl Screen<SurfReport24HDataParams> for SurfReport24HData {
         let output_settings = OutputSettingsBuilder::new().scale(1).build();
         let output_image = display.to_rgb_output_image(&output_settings);
         let image_buffer = output_image.as_image_buffer();
-        image_buffer.write_to(writer, image::ImageFormat::Qoi).unwrap();
+        image_buffer.write_to(writer, image::ImageFormat::Qoi)?;
         Ok(())
     }
 
     fn parse_params(params: &HashMap<String, Value>) -> Result<SurfReport24HDataParams> {
-        let spot_id = params.get("spot_id").unwrap().as_str().unwrap();
+        let spot_id = params
+            .get("spot_id")
+            .and_then(|v| v.as_str())
+            .ok_or(anyhow!("Missing \"spot_id\" param"))?;
+        let qr_code = params.get("qr_code").and_then(|v| v.as_bool()).unwrap_or(false);
+        let wetsuits = match params.get("wetsuits") {
+            Some(v) => serde_json::from_value(v.clone())?,
+            None => WetsuitTable::default(),
+        };
+        let preferences = match params.get("preferences") {
+            Some(v) => serde_json::from_value(v.clone())?,
+            None => SessionPreferences::default(),
+        };
         Ok(SurfReport24HDataParams {
             spot_id: spot_id.into(),
+            qr_code,
+            wetsuits,
+            preferences,
         })
     }
 
@@ -86,51 +160,110 @@ impl Screen<SurfReport24HDataParams> for SurfReport24HData {
 }
 
 impl SurfReport24HData {
+    #[allow(clippy::too_many_arguments)]
     pub fn new_from_results(
-        wave_result: WaveResult,
-        tide_result: TideResult,
-        weather_result: WeatherResult,
-        wind_result: WindResult,
-        conditions_result: ConditionsResult,
-        spot_details_result: SpotDetailsResult,
+        wave_result: Option<WaveResult>,
+        tide_result: Option<TideResult>,
+        weather_result: Option<WeatherResult>,
+        wind_result: Option<WindResult>,
+        conditions_result: Option<ConditionsResult>,
+        spot_details_result: Option<SpotDetailsResult>,
+        report_result: Option<ReportResult>,
+        now: DateTime<Utc>,
     ) -> Self {
-        let now = Utc::now();
+        let utc_offset = spot_utc_offset(tide_result.as_ref(), weather_result.as_ref(), wind_result.as_ref());
+        let today = get_local_time_from_unix(now.timestamp(), utc_offset).date();
+        let astronomy = match &spot_details_result {
+            Some(spot_details) => today
+                .iter_days()
+                .take(2)
+                .map(|date| DayAstronomy::new(date, utc_offset, spot_details.spot.lat, spot_details.spot.lon))
+                .collect(),
+            None => Vec::new(),
+        };
         SurfReport24HData {
             last_updated_utc: now.timestamp(),
-            waves: wave_result
-                .data
-                .wave
-                .into_iter()
-                .skip(6)
-                .step_by(3)
-                .take(MEASUREMENTS_WAVE)
-                .collect(),
-            tides: tide_result
-                .data
-                .tides
-                .into_iter()
-                .skip(6)
-                .take(MEASUREMENTS_TIDE)
-                .collect(),
-            weather: weather_result
-                .data
-                .weather
-                .into_iter()
-                .skip(6)
-                .step_by(3)
-                .take(MEASUREMENTS_WEATHER)
-                .collect(),
-            wind: wind_result
-                .data
-                .wind
-                .into_iter()
-                .skip(6)
-                .step_by(3)
-                .take(MEASUREMENTS_WIND)
-                .collect(),
-            conditions: conditions_result.data.conditions.into_iter().next().unwrap(),
-            spot_details: spot_details_result.spot,
+            waves: wave_result.map(|wave_result| {
+                wave_result
+                    .data
+                    .wave
+                    .into_iter()
+                    .skip(6)
+                    .step_by(3)
+                    .take(MEASUREMENTS_WAVE)
+                    .collect()
+            }),
+            tides: tide_result.map(|tide_result| {
+                tide_result
+                    .data
+                    .tides
+                    .into_iter()
+                    .skip(6)
+                    .take(MEASUREMENTS_TIDE)
+                    .collect()
+            }),
+            weather: weather_result.map(|weather_result| {
+                weather_result
+                    .data
+                    .weather
+                    .into_iter()
+                    .skip(6)
+                    .step_by(3)
+                    .take(MEASUREMENTS_WEATHER)
+                    .collect()
+            }),
+            wind: wind_result.map(|wind_result| {
+                wind_result
+                    .data
+                    .wind
+                    .into_iter()
+                    .skip(6)
+                    .step_by(3)
+                    .take(MEASUREMENTS_WIND)
+                    .collect()
+            }),
+            conditions: conditions_result
+                .and_then(|conditions_result| conditions_result.data.conditions.into_iter().next()),
+            spot_details: spot_details_result.map(|spot_details_result| spot_details_result.spot),
+            water_temperature: report_result.map(|report_result| report_result.water_temperature()),
+            wetsuit: None,
+            locale: Locale::default(),
+            astronomy,
+            best_sessions: Vec::new(),
+            qr_code: None,
+        }
+    }
+
+    /// Whether any forecast section was fetched, spot details alone aren't worth publishing
+    pub fn has_forecast(&self) -> bool {
+        self.waves.is_some()
+            || self.tides.is_some()
+            || self.weather.is_some()
+            || self.wind.is_some()
+            || self.conditions.is_some()
+    }
+
+    /// First and last timestamp of the chart, the tides when there are any, otherwise the same number of hours
+    /// from the first measurement of another section
+    pub fn chart_span(&self) -> (i64, i64) {
+        let tides = self.tides.as_deref().unwrap_or_default();
+        if let (Some(min_time), Some(max_time)) = (
+            tides.iter().map(|tide| tide.timestamp).min(),
+            tides.iter().map(|tide| tide.timestamp).max(),
+        ) && min_time < max_time
+        {
+            return (min_time, max_time);
         }
+        let min_time = [
+            self.waves.as_deref().and_then(|waves| waves.first()).map(|wave| wave.timestamp),
+            self.weather.as_deref().and_then(|weather| weather.first()).map(|weather| weather.timestamp),
+            self.wind.as_deref().and_then(|wind| wind.first()).map(|wind| wind.timestamp),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(self.last_updated_utc);
+        (min_time, min_time + (MEASUREMENTS_TIDE as i64 - 1) * 3600)
     }
 
     pub fn parse_timestamp_utc(&self) -> Result<DateTime<Utc>> {
@@ -1,16 +1,14 @@
-use chrono::Timelike;
+use chrono::{DateTime, Timelike, Utc};
 
 use crate::common::draw_utils::{
-    draw_binary_image_on_tricolor, draw_last_updated, draw_weather_icon, format_wave_height,
-    format_wind_speed, get_local_time_from_unix, left_text_style,
+    centered_text_style, draw_binary_image_on_tricolor, draw_last_updated, draw_moon_phase, draw_no_data,
+    draw_qr_code, draw_water_temperature, draw_weather_icon, format_local_time, format_wave_height,
+    format_wind_speed, get_local_time_from_unix, left_text_style, qr_code_size,
 };
 use crate::image_data::{WAVE, WIND};
 use core::fmt::Debug;
 use core::fmt::Write;
-use embedded_graphics::image::ImageRaw;
-use embedded_graphics::mono_font::ascii::{FONT_7X13, FONT_8X13, FONT_9X15, FONT_9X15_BOLD};
-use embedded_graphics::mono_font::iso_8859_10::FONT_10X20;
-use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::mono_font::iso_8859_1::{FONT_6X10, FONT_7X13, FONT_8X13, FONT_9X15, FONT_9X15_BOLD, FONT_10X20};
 use embedded_graphics::primitives::{Line, Polyline, PrimitiveStyle};
 use embedded_graphics::{
     mono_font::MonoTextStyle,
@@ -18,12 +16,14 @@ use embedded_graphics::{
     text::{Alignment, LineHeight, Text, TextStyleBuilder},
 };
 use epd_waveshare::color::TriColor;
+use tracing::warn;
 const TIDE_CHART_X_LEFT: i32 = 50;
 const TIDE_CHART_X_RIGHT: i32 = 760;
 const TIDE_CHART_WIDTH: i32 = TIDE_CHART_X_RIGHT - TIDE_CHART_X_LEFT;
 const TIDE_CHART_Y_TOP: i32 = 100;
 const TIDE_CHART_Y_BOTTOM: i32 = 220;
 const TIDE_Y_HEIGHT: i32 = TIDE_CHART_Y_BOTTOM - TIDE_CHART_Y_TOP;
+const QR_CODE_MODULE_SIZE: u32 = 2;
 
 use crate::surf_report_24h::data::SurfReport24HData;
 use crate::surfline_types::wind::WindDirectionType;
@@ -34,25 +34,46 @@ where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    let (min_time, max_time) = draw_tides(target, &surf_report)?;
+    let (min_time, max_time) = surf_report.chart_span();
+    draw_tides(target, surf_report, min_time, max_time)?;
+    draw_daylight(target, surf_report, min_time, max_time)?;
+    draw_best_sessions(target, surf_report, min_time, max_time)?;
     draw_weather(target, surf_report, min_time, max_time, 350)?;
     draw_wind(target, surf_report, min_time, max_time, 400)?;
     draw_wave_height(target, &surf_report, min_time, max_time, 450)?;
     draw_headings(target, &surf_report, 20)?;
-    draw_last_updated(target, &surf_report.parse_timestamp_local().unwrap())?;
+    draw_sun_and_moon(target, surf_report, 60)?;
+    // QR code linking to the full forecast in the top right corner
+    if let Some(url) = &surf_report.qr_code {
+        match qr_code_size(url, QR_CODE_MODULE_SIZE) {
+            Ok(size) => draw_qr_code(target, url, Point::new(800 - size as i32 - 4, 4), QR_CODE_MODULE_SIZE)?,
+            Err(e) => warn!(url, "Drawing without QR code: {:#}", e),
+        }
+    }
+    draw_last_updated(
+        target,
+        surf_report.locale.messages(),
+        &surf_report.parse_timestamp_local().unwrap(),
+    )?;
     Ok(())
 }
 
-pub fn draw_tides<D, E>(target: &mut D, surf_report: &SurfReport24HData) -> Result<(i64, i64), E>
+pub fn draw_tides<D, E>(target: &mut D, surf_report: &SurfReport24HData, min_time: i64, max_time: i64) -> Result<(), E>
 where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
+    let messages = surf_report.locale.messages();
+    let Some(tides) = surf_report.tides.as_deref().filter(|tides| !tides.is_empty()) else {
+        let center = Point::new(
+            (TIDE_CHART_X_LEFT + TIDE_CHART_X_RIGHT) / 2,
+            (TIDE_CHART_Y_TOP + TIDE_CHART_Y_BOTTOM) / 2,
+        );
+        return draw_no_data(target, messages, center, centered_text_style());
+    };
     // find max and min
-    let mut min_height: f32 = surf_report.tides.iter().map(|f| f.height).reduce(f32::min).unwrap();
-    let mut max_height: f32 = surf_report.tides.iter().map(|f| f.height).reduce(f32::max).unwrap();
-    let min_time: i64 = surf_report.tides.iter().map(|f| f.timestamp).reduce(i64::min).unwrap();
-    let max_time: i64 = surf_report.tides.iter().map(|f| f.timestamp).reduce(i64::max).unwrap();
+    let mut min_height: f32 = tides.iter().map(|f| f.height).reduce(f32::min).unwrap();
+    let mut max_height: f32 = tides.iter().map(|f| f.height).reduce(f32::max).unwrap();
     let mut negative_adjustment = 0.;
     if min_height < 0. {
         negative_adjustment = -min_height;
@@ -64,15 +85,15 @@ where
 
     let mut idx: usize = 0;
     let mut skip_next_ts = false;
-    for pred in &surf_report.tides {
+    for pred in tides {
         // if next data point is low/high tide, skip drawing this one
-        if idx < surf_report.tides.len() - 1 && surf_report.tides[idx + 1].r#type.is_high_low() {
+        if idx < tides.len() - 1 && tides[idx + 1].r#type.is_high_low() {
             idx += 1;
             continue;
         }
 
         // if previous data point is high/low, skip drawing this one
-        if idx > 0 && surf_report.tides[idx - 1].r#type.is_high_low() {
+        if idx > 0 && tides[idx - 1].r#type.is_high_low() {
             idx += 1;
             continue;
         }
@@ -94,14 +115,13 @@ where
 
         // show timestamp only if it is a low/high tide, or a weather event
         if !skip_next_ts && (pred.r#type.is_high_low() || local_time.hour() % 3 == 0) {
-            let mut time_label: String = String::new();
-            if pred.r#type.is_high_low() {
+            let time_label = if pred.r#type.is_high_low() {
                 // show minutes for high/low tide
-                write!(time_label, "{:.2}:{:02}", local_time.hour(), local_time.minute()).unwrap();
                 skip_next_ts = true;
+                messages.format_time(local_time.hour(), local_time.minute())
             } else {
-                write!(time_label, "{:.2}", local_time.hour()).unwrap();
-            }
+                messages.format_hour(local_time.hour())
+            };
             Text::with_text_style(
                 time_label.as_str(),
                 Point::new(x_axis - 5, TIDE_CHART_Y_BOTTOM + 50),
@@ -126,7 +146,7 @@ where
 
         if pred.r#type.is_high_low() {
             let mut txt: String = String::new();
-            write!(txt, "{:.1}ft", pred.height).unwrap();
+            write!(txt, "{:.1}{}", pred.height, messages.feet).unwrap();
             Text::with_text_style(
                 txt.as_str(),
                 Point::new(x_axis - 5, screen_height - 20),
@@ -145,7 +165,7 @@ where
     Polyline::new(&points)
         .into_styled(PrimitiveStyle::with_stroke(TriColor::Black, 3))
         .draw(target)?;
-    Ok((min_time, max_time))
+    Ok(())
 }
 
 pub fn draw_wave_height<D, E>(
@@ -159,14 +179,17 @@ where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    draw_binary_image_on_tricolor(&ImageRaw::<BinaryColor>::new(WAVE, 32), Point::new(10, y - 16), target);
+    draw_binary_image_on_tricolor(&WAVE, Point::new(10, y - 16), target);
 
     let text_style = left_text_style();
-    for data in surf_report.waves.iter().take(10) {
+    let Some(waves) = &surf_report.waves else {
+        return draw_no_data(target, surf_report.locale.messages(), Point::new(TIDE_CHART_X_LEFT, y), text_style);
+    };
+    for data in waves.iter().take(10) {
         let x_axis_proportion = (data.timestamp as f64 - min_time as f64) / (max_time - min_time) as f64;
         let x_axis = (TIDE_CHART_X_LEFT as f64 + (TIDE_CHART_WIDTH as f64) * x_axis_proportion) as i32;
 
-        let wave_text = format_wave_height(data.surf.min, data.surf.max);
+        let wave_text = format_wave_height(surf_report.locale.messages(), data.surf.min, data.surf.max);
         Text::with_text_style(
             wave_text.as_str(),
             Point::new(x_axis - 10, y),
@@ -189,15 +212,17 @@ where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    draw_binary_image_on_tricolor(&ImageRaw::<BinaryColor>::new(WIND, 32), Point::new(10, y - 16), target);
+    draw_binary_image_on_tricolor(&WIND, Point::new(10, y - 16), target);
 
     let text_style = left_text_style();
-
-    for data in surf_report.wind.iter().take(10) {
+    let Some(wind) = &surf_report.wind else {
+        return draw_no_data(target, surf_report.locale.messages(), Point::new(TIDE_CHART_X_LEFT, y), text_style);
+    };
+    for data in wind.iter().take(10) {
         let x_axis_proportion = (data.timestamp as f64 - min_time as f64) / (max_time - min_time) as f64;
         let x_axis = (TIDE_CHART_X_LEFT as f64 + (TIDE_CHART_WIDTH as f64) * x_axis_proportion) as i32;
 
-        let wind_text = format_wind_speed(data.speed);
+        let wind_text = format_wind_speed(surf_report.locale.messages(), data.speed);
         Text::with_text_style(
             wind_text.as_str(),
             Point::new(x_axis - 10, y),
@@ -258,7 +283,10 @@ where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    for data in surf_report.weather.iter().take(10) {
+    let Some(weather) = &surf_report.weather else {
+        return draw_no_data(target, surf_report.locale.messages(), Point::new(TIDE_CHART_X_LEFT, y), left_text_style());
+    };
+    for data in weather.iter().take(10) {
         let x_axis_proportion = (data.timestamp as f64 - min_time as f64) / (max_time - min_time) as f64;
         let x_axis = (TIDE_CHART_X_LEFT as f64 + (TIDE_CHART_WIDTH as f64) * x_axis_proportion) as i32;
 
@@ -274,15 +302,26 @@ where
     D: DrawTarget<Color = TriColor, Error = E>,
 {
     let text_style = left_text_style();
+    let messages = surf_report.locale.messages();
+    let name = surf_report.spot_details.as_ref().map_or(messages.no_data, |spot| spot.name.as_str());
     Text::with_text_style(
-        surf_report.spot_details.name.as_str(),
+        name,
         Point::new(10, y),
         MonoTextStyle::new(&FONT_10X20, TriColor::Black),
         text_style,
     )
     .draw(target)?;
+    let name_width = (name.chars().count() as i32 + 2) * 10;
+    draw_water_temperature(
+        target,
+        messages,
+        surf_report.water_temperature.as_ref(),
+        surf_report.wetsuit.as_deref(),
+        Point::new(10 + name_width, y),
+    )?;
+    let headline = surf_report.conditions.as_ref().map_or(messages.no_data, |conditions| conditions.headline.as_str());
     Text::with_text_style(
-        surf_report.conditions.headline.as_str(),
+        headline,
         Point::new(10, y + 20),
         MonoTextStyle::new(&FONT_8X13, TriColor::Black),
         text_style,
@@ -291,3 +330,104 @@ where
     Ok(())
 }
 
+
+/// Strip under the tide chart, black at night and chromatic during civil twilight
+pub fn draw_daylight<D, E>(target: &mut D, surf_report: &SurfReport24HData, min_time: i64, max_time: i64) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    for x in TIDE_CHART_X_LEFT..=TIDE_CHART_X_RIGHT {
+        let timestamp = min_time + (max_time - min_time) * (x - TIDE_CHART_X_LEFT) as i64 / TIDE_CHART_WIDTH as i64;
+        let Some(day) = surf_report
+            .astronomy
+            .iter()
+            .find(|day| get_local_time_from_unix(timestamp, day.utc_offset).date() == day.date)
+        else {
+            continue;
+        };
+        let is_before = |time: Option<DateTime<Utc>>| time.is_some_and(|time| timestamp < time.timestamp());
+        let is_after = |time: Option<DateTime<Utc>>| time.is_some_and(|time| timestamp > time.timestamp());
+        let color = if is_before(day.sun.civil_dawn) || is_after(day.sun.civil_dusk) {
+            TriColor::Black
+        } else if is_before(day.sun.sunrise) || is_after(day.sun.sunset) {
+            TriColor::Chromatic
+        } else {
+            continue;
+        };
+        Line::new(
+            Point::new(x, TIDE_CHART_Y_BOTTOM + 4),
+            Point::new(x, TIDE_CHART_Y_BOTTOM + 8),
+        )
+        .into_styled(PrimitiveStyle::with_stroke(color, 1))
+        .draw(target)?;
+    }
+    Ok(())
+}
+
+/// Bars under the tide chart spanning the best windows, labelled in ranking order
+pub fn draw_best_sessions<D, E>(
+    target: &mut D,
+    surf_report: &SurfReport24HData,
+    min_time: i64,
+    max_time: i64,
+) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    let to_x = |timestamp: i64| {
+        let proportion = (timestamp - min_time) as f64 / (max_time - min_time) as f64;
+        (TIDE_CHART_X_LEFT as f64 + TIDE_CHART_WIDTH as f64 * proportion) as i32
+    };
+    let messages = surf_report.locale.messages();
+    let labels = [messages.best_session, messages.second_session];
+    for (session, label) in surf_report.best_sessions.iter().zip(labels) {
+        let (x_start, x_end) = (to_x(session.start).max(TIDE_CHART_X_LEFT), to_x(session.end).min(TIDE_CHART_X_RIGHT));
+        Line::new(
+            Point::new(x_start, TIDE_CHART_Y_BOTTOM + 14),
+            Point::new(x_end, TIDE_CHART_Y_BOTTOM + 14),
+        )
+        .into_styled(PrimitiveStyle::with_stroke(TriColor::Chromatic, 5))
+        .draw(target)?;
+        Text::with_text_style(
+            label,
+            Point::new(x_start, TIDE_CHART_Y_BOTTOM + 28),
+            MonoTextStyle::new(&FONT_6X10, TriColor::Chromatic),
+            left_text_style(),
+        )
+        .draw(target)?;
+    }
+    Ok(())
+}
+
+/// Today's sunrise, sunset and moonrise followed by the moon phase
+pub fn draw_sun_and_moon<D, E>(target: &mut D, surf_report: &SurfReport24HData, y: i32) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    let Some(today) = surf_report.astronomy.first() else {
+        return Ok(());
+    };
+    let messages = surf_report.locale.messages();
+    let text = format!(
+        "{} {}  {} {}  {} {}",
+        messages.sunrise,
+        format_local_time(messages, today.sun.sunrise, today.utc_offset),
+        messages.sunset,
+        format_local_time(messages, today.sun.sunset, today.utc_offset),
+        messages.moonrise,
+        format_local_time(messages, today.moon.rise, today.utc_offset),
+    );
+    Text::with_text_style(
+        text.as_str(),
+        Point::new(10, y),
+        MonoTextStyle::new(&FONT_8X13, TriColor::Black),
+        left_text_style(),
+    )
+    .draw(target)?;
+    let text_width = text.chars().count() as i32 * 8;
+    draw_moon_phase(target, &today.moon_phase, Point::new(10 + text_width + 14, y - 4), 7)?;
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use chrono::prelude::*;
 use core::fmt::Debug;
 use embedded_graphics::prelude::*;
@@ -10,47 +10,97 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    screen::Screen,
+    astronomy::DayAstronomy,
+    common::draw_utils::get_local_time_from_unix,
+    locale::Locale,
+    screen::{RenderContext, Screen},
+    scoring::{SessionPreferences, SessionWindow, best_window_per_day, daylight_only, score_hours},
     surf_report_week::draw::draw,
     surfline_types::{
-        common::FetchParams,
-        spot_details::{SpotDetails, SpotDetailsResult, fetch_spot_details},
+        common::{FetchParams, optional_section, spot_utc_offset},
+        report::{ReportResult, WaterTemperature, fetch_report},
+        spot_details::{SpotDetails, SpotDetailsResult, fetch_spot_details, surfline_spot_url},
         tide::{TideMeasurement, TideResult, fetch_tides},
         wave::{WaveMeasurement, WaveResult, fetch_waves},
         weather::{WeatherMeasurement, WeatherResult, fetch_weather},
         wind::{WindMeasurement, WindResult, fetch_wind},
     },
+    wetsuit::WetsuitTable,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SurfReportWeekParams {
     spot_id: String,
+    qr_code: bool,
+    wetsuits: WetsuitTable,
+    preferences: SessionPreferences,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SurfReportWeekData {
     pub last_updated_utc: i64,
-    pub waves: Vec<WaveMeasurement>,
-    pub tides: Vec<TideMeasurement>,
-    pub weather: Vec<WeatherMeasurement>,
-    pub wind: Vec<WindMeasurement>,
-    pub spot_details: SpotDetails,
+    // local date of the first of the seven columns
+    pub first_day: NaiveDate,
+    // each section is None when its fetch failed and is drawn as "no data"
+    pub waves: Option<Vec<WaveMeasurement>>,
+    pub tides: Option<Vec<TideMeasurement>>,
+    pub weather: Option<Vec<WeatherMeasurement>>,
+    pub wind: Option<Vec<WindMeasurement>>,
+    pub spot_details: Option<SpotDetails>,
+    pub water_temperature: Option<WaterTemperature>,
+    pub wetsuit: Option<String>,
+    #[serde(default)]
+    pub locale: Locale,
+    // one entry per day of the forecast
+    pub astronomy: Vec<DayAstronomy>,
+    // best window of each day, days without a surfable hour are left out
+    pub best_sessions: Vec<SessionWindow>,
+    // link to the spot's Surfline page, drawn as a QR code in the top right corner
+    pub qr_code: Option<String>,
 }
 
 impl Screen<SurfReportWeekParams> for SurfReportWeekData {
-    async fn from_params(params: &SurfReportWeekParams) -> Result<Box<Self>> {
+    async fn from_params(params: &SurfReportWeekParams, context: &RenderContext) -> Result<Box<Self>> {
         let spot_id = params.spot_id.as_str();
+        let source = &context.data_source;
         let fetch_params = FetchParams {
             days: 7,
             interval_hours: 3,
         };
-        Ok(Box::new(SurfReportWeekData::new_from_results(
-            fetch_waves(spot_id, Some(fetch_params.clone())).await?,
-            fetch_tides(spot_id, Some(fetch_params.clone())).await?,
-            fetch_weather(spot_id, Some(fetch_params.clone())).await?,
-            fetch_wind(spot_id, Some(fetch_params.clone())).await?,
-            fetch_spot_details(spot_id).await?,
-        )))
+        let mut data = SurfReportWeekData::new_from_results(
+            optional_section(spot_id, "waves", fetch_waves(source, spot_id, Some(fetch_params.clone())).await),
+            optional_section(spot_id, "tides", fetch_tides(source, spot_id, Some(fetch_params.clone())).await),
+            optional_section(spot_id, "weather", fetch_weather(source, spot_id, Some(fetch_params.clone())).await),
+            optional_section(spot_id, "wind", fetch_wind(source, spot_id, Some(fetch_params.clone())).await),
+            optional_section(spot_id, "spot details", fetch_spot_details(source, spot_id).await),
+            optional_section(spot_id, "report", fetch_report(source, spot_id).await),
+            context.now(),
+        );
+        if !data.has_forecast() {
+            return Err(anyhow!("No forecast data for spot {}", spot_id));
+        }
+        data.locale = context.locale;
+        data.wetsuit = data
+            .water_temperature
+            .as_ref()
+            .and_then(|water_temperature| params.wetsuits.recommend(water_temperature.average_fahrenheit()))
+            .map(|suit| suit.to_string());
+        if let Some(waves) = &data.waves {
+            let scores = score_hours(
+                waves,
+                data.wind.as_deref().unwrap_or_default(),
+                data.tides.as_deref().unwrap_or_default(),
+                &params.preferences,
+            );
+            data.best_sessions = best_window_per_day(&daylight_only(&scores, &data.astronomy));
+        }
+        if params.qr_code {
+            data.qr_code = data
+                .spot_details
+                .as_ref()
+                .map(|spot| surfline_spot_url(spot_id, &spot.name));
+        }
+        Ok(Box::new(data))
     }
 
     fn draw_to_qoi<W>(&self, writer: &mut W) -> Result<()>
@@ -62,14 +112,29 @@ impl Screen<SurfReportWeekParams> for SurfReportWeekData {
         let output_settings = OutputSettingsBuilder::new().scale(1).build();
         let output_image = display.to_rgb_output_image(&output_settings);
         let image_buffer = output_image.as_image_buffer();
-        image_buffer.write_to(writer, image::ImageFormat::Qoi).unwrap();
+        image_buffer.write_to(writer, image::ImageFormat::Qoi)?;
         Ok(())
     }
 
     fn parse_params(params: &HashMap<String, Value>) -> Result<SurfReportWeekParams> {
-        let spot_id = params.get("spot_id").unwrap().as_str().unwrap();
+        let spot_id = params
+            .get("spot_id")
+            .and_then(|v| v.as_str())
+            .ok_or(anyhow!("Missing \"spot_id\" param"))?;
+        let qr_code = params.get("qr_code").and_then(|v| v.as_bool()).unwrap_or(false);
+        let wetsuits = match params.get("wetsuits") {
+            Some(v) => serde_json::from_value(v.clone())?,
+            None => WetsuitTable::default(),
+        };
+        let preferences = match params.get("preferences") {
+            Some(v) => serde_json::from_value(v.clone())?,
+            None => SessionPreferences::default(),
+        };
         Ok(SurfReportWeekParams {
             spot_id: spot_id.into(),
+            qr_code,
+            wetsuits,
+            preferences,
         })
     }
 
@@ -84,23 +149,65 @@ impl Screen<SurfReportWeekParams> for SurfReportWeekData {
 
 impl SurfReportWeekData {
     pub fn new_from_results(
-        wave_result: WaveResult,
-        tide_result: TideResult,
-        weather_result: WeatherResult,
-        wind_result: WindResult,
-        spot_details_result: SpotDetailsResult,
+        wave_result: Option<WaveResult>,
+        tide_result: Option<TideResult>,
+        weather_result: Option<WeatherResult>,
+        wind_result: Option<WindResult>,
+        spot_details_result: Option<SpotDetailsResult>,
+        report_result: Option<ReportResult>,
+        now: DateTime<Utc>,
     ) -> Self {
-        let now = Utc::now();
+        let utc_offset = spot_utc_offset(tide_result.as_ref(), weather_result.as_ref(), wind_result.as_ref());
+        // the waves decide the columns, the other forecasts stand in when they are missing
+        let first_timestamp = wave_result
+            .as_ref()
+            .and_then(|wave_result| wave_result.data.wave.first().map(|wave| wave.timestamp))
+            .or_else(|| {
+                [
+                    tide_result.as_ref().and_then(|result| result.data.tides.first().map(|tide| tide.timestamp)),
+                    weather_result.as_ref().and_then(|result| result.data.weather.first().map(|hour| hour.timestamp)),
+                    wind_result.as_ref().and_then(|result| result.data.wind.first().map(|wind| wind.timestamp)),
+                ]
+                .into_iter()
+                .flatten()
+                .min()
+            });
+        let first_day = get_local_time_from_unix(first_timestamp.unwrap_or(now.timestamp()), utc_offset).date();
+        let astronomy = match &spot_details_result {
+            Some(spot_details) => first_day
+                .iter_days()
+                .take(8)
+                .map(|date| DayAstronomy::new(date, utc_offset, spot_details.spot.lat, spot_details.spot.lon))
+                .collect(),
+            None => Vec::new(),
+        };
         SurfReportWeekData {
             last_updated_utc: now.timestamp(),
-            waves: wave_result.data.wave.into_iter().collect(),
-            tides: tide_result.data.tides.into_iter().collect(),
-            weather: weather_result.data.weather.into_iter().step_by(3).collect(),
-            wind: wind_result.data.wind.into_iter().collect(),
-            spot_details: spot_details_result.spot,
+            first_day,
+            waves: wave_result.map(|wave_result| wave_result.data.wave),
+            tides: tide_result.map(|tide_result| tide_result.data.tides),
+            weather: weather_result.map(|weather_result| weather_result.data.weather.into_iter().step_by(3).collect()),
+            wind: wind_result.map(|wind_result| wind_result.data.wind),
+            spot_details: spot_details_result.map(|spot_details_result| spot_details_result.spot),
+            water_temperature: report_result.map(|report_result| report_result.water_temperature()),
+            wetsuit: None,
+            locale: Locale::default(),
+            astronomy,
+            best_sessions: Vec::new(),
+            qr_code: None,
         }
     }
 
+    /// Whether any forecast section was fetched, spot details alone aren't worth publishing
+    pub fn has_forecast(&self) -> bool {
+        self.waves.is_some() || self.tides.is_some() || self.weather.is_some() || self.wind.is_some()
+    }
+
+    /// Dates of the seven columns
+    pub fn days(&self) -> Vec<NaiveDate> {
+        self.first_day.iter_days().take(7).collect()
+    }
+
     pub fn parse_timestamp_utc(&self) -> Result<DateTime<Utc>> {
         Ok(Utc.timestamp_opt(self.last_updated_utc, 0).unwrap())
     }
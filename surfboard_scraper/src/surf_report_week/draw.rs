@@ -1,11 +1,13 @@
 use chrono::{Datelike, NaiveDate, TimeZone, Timelike, Utc};
 
 use crate::common::draw_utils::{
-    centered_text_style, draw_last_updated, draw_small_text, draw_text, draw_weather_icon, format_temperature_range,
-    format_wave_height, format_wind_speed, get_local_time_from_unix,
+    centered_text_style, draw_last_updated, draw_moon_phase, draw_no_data, draw_qr_code, draw_small_text,
+    draw_text, draw_water_temperature, draw_weather_icon, format_local_time, format_temperature_range,
+    format_wave_height, format_wind_speed, get_local_time_from_unix, left_text_style, qr_code_size,
 };
+use crate::locale::Messages;
 use core::fmt::Debug;
-use embedded_graphics::mono_font::ascii::FONT_10X20;
+use embedded_graphics::mono_font::iso_8859_1::{FONT_6X10, FONT_10X20};
 use embedded_graphics::{
     mono_font::MonoTextStyle,
     prelude::*,
@@ -14,6 +16,7 @@ use embedded_graphics::{
 };
 use epd_waveshare::color::TriColor;
 use std::collections::HashMap;
+use tracing::warn;
 
 // Weekly layout constants - 7 columns for days of the week
 const CHART_X_LEFT: i32 = 40;
@@ -30,11 +33,21 @@ const WEATHER_DATA_Y: i32 = 255; // Weather icons
 const TEMPERATURE_DATA_Y: i32 = 285; // Temperature text below weather icons
 const WIND_DATA_Y: i32 = 325; // Wind speed data
 const TIDE_DATA_Y: i32 = 350; // Tide times data
+const MOON_DATA_Y: i32 = 395; // Moon phase glyph
+const DAYLIGHT_DATA_Y: i32 = 420; // Sunrise and sunset
 
 // Separator styling constants - span the main content area
 const SEPARATOR_TOP_Y: i32 = 100;
 const SEPARATOR_BOTTOM_Y: i32 = 370;
 
+const QR_CODE_MODULE_SIZE: u32 = 2;
+const HEADING_Y: i32 = 30;
+
+/// Where a row whose data could not be fetched says so, in the middle column
+fn no_data_position(y: i32) -> Point {
+    Point::new((CHART_X_LEFT + CHART_X_RIGHT) / 2, y)
+}
+
 use crate::surf_report_week::data::SurfReportWeekData;
 
 pub fn draw<D, E>(target: &mut D, surf_report: &SurfReportWeekData) -> Result<(), E>
@@ -45,17 +58,34 @@ where
     // Draw day separators first (as background elements)
     draw_day_separators(target)?;
 
+    // Draw spot name and water temperature
+    draw_heading(target, surf_report)?;
+
     // Draw day column headers
     draw_day_headers(target, surf_report)?;
 
     // Draw daily data in columns
     draw_daily_waves(target, surf_report)?;
+    draw_daily_best_sessions(target, surf_report)?;
     draw_daily_weather(target, surf_report)?;
     draw_daily_wind(target, surf_report)?;
     draw_daily_tides(target, surf_report)?;
+    draw_daily_astronomy(target, surf_report)?;
+
+    // QR code linking to the full forecast in the top right corner
+    if let Some(url) = &surf_report.qr_code {
+        match qr_code_size(url, QR_CODE_MODULE_SIZE) {
+            Ok(size) => draw_qr_code(target, url, Point::new(800 - size as i32 - 4, 4), QR_CODE_MODULE_SIZE)?,
+            Err(e) => warn!(url, "Drawing without QR code: {:#}", e),
+        }
+    }
 
     // Draw footer
-    draw_last_updated(target, &surf_report.parse_timestamp_local().unwrap())?;
+    draw_last_updated(
+        target,
+        surf_report.locale.messages(),
+        &surf_report.parse_timestamp_local().unwrap(),
+    )?;
 
     Ok(())
 }
@@ -190,7 +220,10 @@ fn group_wind_by_day(wind: &[crate::surfline_types::wind::WindMeasurement]) -> V
     daily_summaries
 }
 
-fn group_tides_by_day(tides: &[crate::surfline_types::tide::TideMeasurement]) -> Vec<DailyTideSummary> {
+fn group_tides_by_day(
+    tides: &[crate::surfline_types::tide::TideMeasurement],
+    messages: &Messages,
+) -> Vec<DailyTideSummary> {
     let mut daily_groups: HashMap<NaiveDate, Vec<&crate::surfline_types::tide::TideMeasurement>> = HashMap::new();
 
     // Group measurements by date using local time
@@ -213,7 +246,7 @@ fn group_tides_by_day(tides: &[crate::surfline_types::tide::TideMeasurement]) ->
 
                 // Only include tides between 6AM and 9PM (21:00)
                 if hour >= 5 && hour <= 21 {
-                    let time_str = format!("{}:{:02}", hour, local_time.minute());
+                    let time_str = messages.format_time(hour, local_time.minute());
 
                     // Update highest tide if this is higher
                     if let Some((_, current_highest)) = &highest_tide {
@@ -247,32 +280,45 @@ fn group_tides_by_day(tides: &[crate::surfline_types::tide::TideMeasurement]) ->
     daily_summaries
 }
 
+pub fn draw_heading<D, E>(target: &mut D, surf_report: &SurfReportWeekData) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    let messages = surf_report.locale.messages();
+    Text::with_text_style(
+        surf_report.spot_details.as_ref().map_or(messages.no_data, |spot| spot.name.as_str()),
+        Point::new(CHART_X_LEFT, HEADING_Y),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Black),
+        left_text_style(),
+    )
+    .draw(target)?;
+    draw_water_temperature(
+        target,
+        messages,
+        surf_report.water_temperature.as_ref(),
+        surf_report.wetsuit.as_deref(),
+        Point::new(CHART_X_LEFT, HEADING_Y + 25),
+    )?;
+    Ok(())
+}
+
 pub fn draw_day_headers<D, E>(target: &mut D, surf_report: &SurfReportWeekData) -> Result<(), E>
 where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
     let text_style = centered_text_style();
+    let messages = surf_report.locale.messages();
 
-    // Get dates from wave data
-    let daily_waves = group_waves_by_day(&surf_report.waves);
-
-    for (day_index, wave_summary) in daily_waves.iter().enumerate().take(7) {
+    for (day_index, date) in surf_report.days().into_iter().enumerate() {
         let x_pos = CHART_X_LEFT + (day_index as i32 * COLUMN_WIDTH) + (COLUMN_WIDTH / 2);
 
         // Get the weekday name based on the actual date
-        let weekday_name = match wave_summary.date.weekday() {
-            chrono::Weekday::Mon => "Mon",
-            chrono::Weekday::Tue => "Tue",
-            chrono::Weekday::Wed => "Wed",
-            chrono::Weekday::Thu => "Thu",
-            chrono::Weekday::Fri => "Fri",
-            chrono::Weekday::Sat => "Sat",
-            chrono::Weekday::Sun => "Sun",
-        };
+        let weekday_name = messages.weekday(date.weekday());
 
-        // Format date as M/D (e.g., "8/8" for August 8th)
-        let date_str = format!("{}/{}", wave_summary.date.month(), wave_summary.date.day());
+        // Format date as M/D or D/M depending on the locale (e.g., "8/8" for August 8th)
+        let date_str = messages.format_date(date);
 
         // Draw day name
         Text::with_text_style(
@@ -295,27 +341,71 @@ where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    let daily_waves = group_waves_by_day(&surf_report.waves);
     let text_style = centered_text_style();
+    let Some(waves) = &surf_report.waves else {
+        return draw_no_data(target, surf_report.locale.messages(), no_data_position(WAVE_DATA_Y), text_style);
+    };
+    let daily_waves = group_waves_by_day(waves);
 
     for (day_index, wave_summary) in daily_waves.iter().enumerate().take(7) {
         let x_pos = CHART_X_LEFT + (day_index as i32 * COLUMN_WIDTH) + (COLUMN_WIDTH / 2);
 
         // Draw wave height text (e.g., "2-4ft")
-        let height_text = format_wave_height(wave_summary.min_height, wave_summary.max_height);
+        let height_text = format_wave_height(surf_report.locale.messages(), wave_summary.min_height, wave_summary.max_height);
         draw_text(target, &height_text, Point::new(x_pos, WAVE_DATA_Y), text_style)?;
     }
 
     Ok(())
 }
 
+/// Each day's best window under its wave height, e.g. "Best 06-09"
+pub fn draw_daily_best_sessions<D, E>(target: &mut D, surf_report: &SurfReportWeekData) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    let text_style = centered_text_style();
+
+    for (day_index, date) in surf_report.days().into_iter().enumerate() {
+        let Some(session) = surf_report
+            .best_sessions
+            .iter()
+            .find(|session| get_local_time_from_unix(session.start, session.utc_offset).date() == date)
+        else {
+            continue;
+        };
+        let x_pos = CHART_X_LEFT + (day_index as i32 * COLUMN_WIDTH) + (COLUMN_WIDTH / 2);
+        let messages = surf_report.locale.messages();
+        let text = format!(
+            "{} {}",
+            messages.best_session,
+            messages.format_hour_range(
+                get_local_time_from_unix(session.start, session.utc_offset).hour(),
+                get_local_time_from_unix(session.end, session.utc_offset).hour()
+            )
+        );
+        Text::with_text_style(
+            text.as_str(),
+            Point::new(x_pos, WAVE_DATA_Y + 20),
+            MonoTextStyle::new(&FONT_6X10, TriColor::Chromatic),
+            text_style,
+        )
+        .draw(target)?;
+    }
+
+    Ok(())
+}
+
 pub fn draw_daily_weather<D, E>(target: &mut D, surf_report: &SurfReportWeekData) -> Result<(), E>
 where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    let daily_weather = group_weather_by_day(&surf_report.weather);
     let text_style = centered_text_style();
+    let Some(weather) = &surf_report.weather else {
+        return draw_no_data(target, surf_report.locale.messages(), no_data_position(WEATHER_DATA_Y), text_style);
+    };
+    let daily_weather = group_weather_by_day(weather);
 
     for (day_index, weather_summary) in daily_weather.iter().enumerate().take(7) {
         let x_pos = CHART_X_LEFT + (day_index as i32 * COLUMN_WIDTH) + (COLUMN_WIDTH / 2);
@@ -336,14 +426,17 @@ where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    let daily_wind = group_wind_by_day(&surf_report.wind);
     let text_style = centered_text_style();
+    let Some(wind) = &surf_report.wind else {
+        return draw_no_data(target, surf_report.locale.messages(), no_data_position(WIND_DATA_Y), text_style);
+    };
+    let daily_wind = group_wind_by_day(wind);
 
     for (day_index, wind_summary) in daily_wind.iter().enumerate().take(7) {
         let x_pos = CHART_X_LEFT + (day_index as i32 * COLUMN_WIDTH) + (COLUMN_WIDTH / 2);
 
         // Draw wind speed text
-        let speed_text = format_wind_speed(wind_summary.avg_speed);
+        let speed_text = format_wind_speed(surf_report.locale.messages(), wind_summary.avg_speed);
         draw_text(target, &speed_text, Point::new(x_pos, WIND_DATA_Y), text_style)?;
     }
 
@@ -370,8 +463,12 @@ where
     E: Debug,
     D: DrawTarget<Color = TriColor, Error = E>,
 {
-    let daily_tides = group_tides_by_day(&surf_report.tides);
+    let messages = surf_report.locale.messages();
     let text_style = centered_text_style();
+    let Some(tides) = &surf_report.tides else {
+        return draw_no_data(target, messages, no_data_position(TIDE_DATA_Y), text_style);
+    };
+    let daily_tides = group_tides_by_day(tides, messages);
 
     for (day_index, tide_summary) in daily_tides.iter().enumerate().take(7) {
         let x_pos = CHART_X_LEFT + (day_index as i32 * COLUMN_WIDTH) + (COLUMN_WIDTH / 2);
@@ -381,11 +478,11 @@ where
 
         // Show absolute highest and lowest tide times for each day
         if let Some(highest) = &tide_summary.highest_tide {
-            tide_lines.push(format!("H {}", highest));
+            tide_lines.push(format!("{} {}", messages.high_tide, highest));
         }
 
         if let Some(lowest) = &tide_summary.lowest_tide {
-            tide_lines.push(format!("L {}", lowest));
+            tide_lines.push(format!("{} {}", messages.low_tide, lowest));
         }
 
         // Draw each tide on a separate line if we have both
@@ -402,6 +499,50 @@ where
     Ok(())
 }
 
+pub fn draw_daily_astronomy<D, E>(target: &mut D, surf_report: &SurfReportWeekData) -> Result<(), E>
+where
+    E: Debug,
+    D: DrawTarget<Color = TriColor, Error = E>,
+{
+    let text_style = centered_text_style();
+    let messages = surf_report.locale.messages();
+
+    for (day_index, date) in surf_report.days().into_iter().enumerate() {
+        let Some(day) = surf_report.astronomy.iter().find(|day| day.date == date) else {
+            continue;
+        };
+        let x_pos = CHART_X_LEFT + (day_index as i32 * COLUMN_WIDTH) + (COLUMN_WIDTH / 2);
+
+        draw_moon_phase(target, &day.moon_phase, Point::new(x_pos, MOON_DATA_Y), 8)?;
+
+        let daylight_text = format!(
+            "{}-{}",
+            format_local_time(messages, day.sun.sunrise, day.utc_offset),
+            format_local_time(messages, day.sun.sunset, day.utc_offset)
+        );
+        Text::with_text_style(
+            daylight_text.as_str(),
+            Point::new(x_pos, DAYLIGHT_DATA_Y),
+            MonoTextStyle::new(&FONT_6X10, TriColor::Black),
+            text_style,
+        )
+        .draw(target)?;
+
+        // new and full moons bring the biggest tide swings
+        if day.moon_phase.is_spring_tide() {
+            Text::with_text_style(
+                messages.spring_tide,
+                Point::new(x_pos, DAYLIGHT_DATA_Y + 14),
+                MonoTextStyle::new(&FONT_6X10, TriColor::Chromatic),
+                text_style,
+            )
+            .draw(target)?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn draw_day_separators<D, E>(target: &mut D) -> Result<(), E>
 where
     E: Debug,
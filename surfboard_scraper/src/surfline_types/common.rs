@@ -1,3 +1,7 @@
+use tracing::warn;
+
+use crate::surfline_types::{tide::TideResult, weather::WeatherResult, wind::WindResult};
+
 #[derive(Clone)]
 pub struct FetchParams {
     pub days: u32,
@@ -12,3 +16,20 @@ impl Default for FetchParams {
         }
     }
 }
+
+/// A section whose fetch failed is left out of the screen instead of failing it, the error is logged
+pub fn optional_section<T>(spot_id: &str, section: &str, result: anyhow::Result<T>) -> Option<T> {
+    result
+        .map_err(|e| warn!(spot_id, section, "Drawing without section: {:#}", e))
+        .ok()
+}
+
+/// The spot's UTC offset from whichever of the responses was fetched, the tides carry it for the whole
+/// response and weather and wind repeat it on every measurement
+pub fn spot_utc_offset(tides: Option<&TideResult>, weather: Option<&WeatherResult>, wind: Option<&WindResult>) -> i32 {
+    tides
+        .map(|tides| tides.associated.utc_offset)
+        .or_else(|| weather?.data.weather.first().map(|weather| weather.utc_offset))
+        .or_else(|| wind?.data.wind.first().map(|wind| wind.utc_offset))
+        .unwrap_or(0)
+}
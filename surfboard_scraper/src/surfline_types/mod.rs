@@ -1,5 +1,6 @@
 pub mod common;
 pub mod conditions;
+pub mod report;
 pub mod spot_details;
 pub mod tide;
 pub mod wave;
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::http::{DataSource, fetch};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportResult {
+    pub associated: ReportAssociated,
+    pub forecast: ReportForecast,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportAssociated {
+    pub units: ReportUnits,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportUnits {
+    pub temperature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportForecast {
+    pub water_temp: WaterTemperature,
+}
+
+/// Sea surface temperature range for the day, in `unit` ("F" or "C")
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WaterTemperature {
+    pub min: f32,
+    pub max: f32,
+    #[serde(default = "default_unit")]
+    pub unit: String,
+}
+
+fn default_unit() -> String {
+    "F".into()
+}
+
+impl WaterTemperature {
+    /// Midpoint of the range in Fahrenheit
+    pub fn average_fahrenheit(&self) -> f32 {
+        let average = (self.min + self.max) / 2.;
+        if self.unit == "C" { average * 9. / 5. + 32. } else { average }
+    }
+}
+
+impl ReportResult {
+    pub fn water_temperature(&self) -> WaterTemperature {
+        WaterTemperature {
+            unit: self.associated.units.temperature.clone(),
+            ..self.forecast.water_temp.clone()
+        }
+    }
+}
+
+pub async fn fetch_report(source: &DataSource, spot_id: &str) -> Result<ReportResult> {
+    let url = format!("https://services.surfline.com/kbyg/spots/reports?spotId={}", spot_id);
+    fetch(source, url.as_str()).await
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use crate::http::fetch;
+use crate::http::{DataSource, fetch};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SpotDetailsResult {
@@ -11,9 +11,22 @@ pub struct SpotDetailsResult {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SpotDetails {
     pub name: String,
+    pub lat: f64,
+    pub lon: f64,
 }
 
-pub async fn fetch_spot_details(spot_id: &str) -> Result<SpotDetailsResult> {
+pub async fn fetch_spot_details(source: &DataSource, spot_id: &str) -> Result<SpotDetailsResult> {
     let url = format!("https://services.surfline.com/kbyg/spots/details?spotId={}", spot_id);
-    fetch(url.as_str()).await
+    fetch(source, url.as_str()).await
+}
+
+/// Link to the spot's forecast page on surfline.com, the slug is derived from the spot name
+pub fn surfline_spot_url(spot_id: &str, name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-");
+    format!("https://www.surfline.com/surf-report/{}/{}", slug, spot_id)
 }
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use crate::{http::fetch, surfline_types::common::FetchParams};
+use crate::{
+    http::{DataSource, fetch},
+    surfline_types::common::FetchParams,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -52,11 +55,11 @@ pub struct TideMeasurement {
     pub utc_offset: i32,
 }
 
-pub async fn fetch_tides(spot_id: &str, params: Option<FetchParams>) -> Result<TideResult> {
+pub async fn fetch_tides(source: &DataSource, spot_id: &str, params: Option<FetchParams>) -> Result<TideResult> {
     let params = params.unwrap_or_default();
     let url = format!(
         "https://services.surfline.com/kbyg/spots/forecasts/tides?spotId={}&days={}",
         spot_id, params.days
     );
-    fetch(url.as_str()).await
+    fetch(source, url.as_str()).await
 }
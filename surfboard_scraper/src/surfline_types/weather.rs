@@ -1,7 +1,13 @@
 use anyhow::Result;
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use tracing::warn;
 
-use crate::{http::fetch, surfline_types::common::FetchParams};
+use crate::{
+    http::{DataSource, fetch},
+    surfline_types::common::FetchParams,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WeatherResult {
@@ -13,50 +19,135 @@ pub struct WeatherData {
     pub weather: Vec<WeatherMeasurement>,
 }
 
-#[derive(Debug, Serialize, Clone)]
-pub enum WeatherCondition {
-    NightMostlyCloudy,
-    NightCloudy,
-    NightClear,
-    NightFog,
-    MostlyCloudy,
+/// Surfline's weather vocabulary, every kind also comes with a `NIGHT_` prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeatherKind {
+    Clear,
     MostlyClear,
+    PartlyCloudy,
+    MostlyCloudy,
+    Cloudy,
+    Overcast,
+    Fog,
     Mist,
-    Clear,
-    NightMostlyClear,
+    Haze,
+    Smoke,
+    Drizzle,
     BriefShowers,
-    NightBriefShowers,
-    NightMist,
     BriefShowersPossible,
-    NightDrizzle,
-    Drizzle,
+    LightShowers,
+    Showers,
+    HeavyShowers,
+    LightRain,
+    Rain,
+    HeavyRain,
+    ThunderShowers,
+    ThunderStorms,
+    BriefThunderStorms,
+    LightSnow,
+    Snow,
+    HeavySnow,
+    SnowShowers,
+    BriefSnowShowers,
+    BriefSnowShowersPossible,
+    LightSnowShowers,
+    HeavySnowShowers,
+    RainAndSnow,
+    Sleet,
+    Windy,
+}
+
+const WEATHER_KINDS: [(WeatherKind, &str); 33] = [
+    (WeatherKind::Clear, "CLEAR"),
+    (WeatherKind::MostlyClear, "MOSTLY_CLEAR"),
+    (WeatherKind::PartlyCloudy, "PARTLY_CLOUDY"),
+    (WeatherKind::MostlyCloudy, "MOSTLY_CLOUDY"),
+    (WeatherKind::Cloudy, "CLOUDY"),
+    (WeatherKind::Overcast, "OVERCAST"),
+    (WeatherKind::Fog, "FOG"),
+    (WeatherKind::Mist, "MIST"),
+    (WeatherKind::Haze, "HAZE"),
+    (WeatherKind::Smoke, "SMOKE"),
+    (WeatherKind::Drizzle, "DRIZZLE"),
+    (WeatherKind::BriefShowers, "BRIEF_SHOWERS"),
+    (WeatherKind::BriefShowersPossible, "BRIEF_SHOWERS_POSSIBLE"),
+    (WeatherKind::LightShowers, "LIGHT_SHOWERS"),
+    (WeatherKind::Showers, "SHOWERS"),
+    (WeatherKind::HeavyShowers, "HEAVY_SHOWERS"),
+    (WeatherKind::LightRain, "LIGHT_RAIN"),
+    (WeatherKind::Rain, "RAIN"),
+    (WeatherKind::HeavyRain, "HEAVY_RAIN"),
+    (WeatherKind::ThunderShowers, "THUNDER_SHOWERS"),
+    (WeatherKind::ThunderStorms, "THUNDER_STORMS"),
+    (WeatherKind::BriefThunderStorms, "BRIEF_THUNDER_STORMS"),
+    (WeatherKind::LightSnow, "LIGHT_SNOW"),
+    (WeatherKind::Snow, "SNOW"),
+    (WeatherKind::HeavySnow, "HEAVY_SNOW"),
+    (WeatherKind::SnowShowers, "SNOW_SHOWERS"),
+    (WeatherKind::BriefSnowShowers, "BRIEF_SNOW_SHOWERS"),
+    (WeatherKind::BriefSnowShowersPossible, "BRIEF_SNOW_SHOWERS_POSSIBLE"),
+    (WeatherKind::LightSnowShowers, "LIGHT_SNOW_SHOWERS"),
+    (WeatherKind::HeavySnowShowers, "HEAVY_SNOW_SHOWERS"),
+    (WeatherKind::RainAndSnow, "RAIN_AND_SNOW"),
+    (WeatherKind::Sleet, "SLEET"),
+    (WeatherKind::Windy, "WINDY"),
+];
+
+const NIGHT_PREFIX: &str = "NIGHT_";
+
+/// Conditions already reported as unknown, so each is only logged once
+static LOGGED_UNKNOWN: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeatherCondition {
+    Day(WeatherKind),
+    Night(WeatherKind),
     Unknown(String),
 }
 
+impl WeatherCondition {
+    /// Parse one of Surfline's condition strings, e.g. `NIGHT_MOSTLY_CLOUDY`
+    pub fn parse(condition: &str) -> Self {
+        let kind = |name: &str| WEATHER_KINDS.iter().find(|(_, n)| *n == name).map(|(kind, _)| *kind);
+        let parsed = match condition.strip_prefix(NIGHT_PREFIX) {
+            Some(name) => kind(name).map(WeatherCondition::Night),
+            None => kind(condition).map(WeatherCondition::Day),
+        };
+        parsed.unwrap_or_else(|| {
+            if LOGGED_UNKNOWN.lock().unwrap().insert(condition.to_string()) {
+                warn!(condition, "Unknown weather condition, add it to WeatherKind");
+            }
+            WeatherCondition::Unknown(condition.to_string())
+        })
+    }
+
+    /// The Surfline string this condition was parsed from
+    pub fn as_surfline(&self) -> String {
+        let name = |kind: &WeatherKind| WEATHER_KINDS.iter().find(|(k, _)| k == kind).unwrap().1;
+        match self {
+            WeatherCondition::Day(kind) => name(kind).to_string(),
+            WeatherCondition::Night(kind) => format!("{}{}", NIGHT_PREFIX, name(kind)),
+            WeatherCondition::Unknown(condition) => condition.clone(),
+        }
+    }
+}
+
+impl Serialize for WeatherCondition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.as_surfline())
+    }
+}
+
 impl<'de> Deserialize<'de> for WeatherCondition {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "NIGHT_MOSTLY_CLOUDY" => Ok(WeatherCondition::NightMostlyCloudy),
-            "NIGHT_CLOUDY" => Ok(WeatherCondition::NightCloudy),
-            "NIGHT_CLEAR" => Ok(WeatherCondition::NightClear),
-            "NIGHT_FOG" => Ok(WeatherCondition::NightFog),
-            "MOSTLY_CLOUDY" => Ok(WeatherCondition::MostlyCloudy),
-            "MOSTLY_CLEAR" => Ok(WeatherCondition::MostlyClear),
-            "MIST" => Ok(WeatherCondition::Mist),
-            "CLEAR" => Ok(WeatherCondition::Clear),
-            "NIGHT_MOSTLY_CLEAR" => Ok(WeatherCondition::NightMostlyClear),
-            "BRIEF_SHOWERS" => Ok(WeatherCondition::BriefShowers),
-            "NIGHT_BRIEF_SHOWERS" => Ok(WeatherCondition::NightBriefShowers),
-            "NIGHT_MIST" => Ok(WeatherCondition::NightMist),
-            "BRIEF_SHOWERS_POSSIBLE" => Ok(WeatherCondition::BriefShowersPossible),
-            "NIGHT_DRIZZLE" => Ok(WeatherCondition::NightDrizzle),
-            "DRIZZLE" => Ok(WeatherCondition::Drizzle),
-            _ => Ok(WeatherCondition::Unknown(s)),
-        }
+        Ok(WeatherCondition::parse(&s))
     }
 }
 
@@ -69,11 +160,11 @@ pub struct WeatherMeasurement {
     pub temperature: f32,
 }
 
-pub async fn fetch_weather(spot_id: &str, params: Option<FetchParams>) -> Result<WeatherResult> {
+pub async fn fetch_weather(source: &DataSource, spot_id: &str, params: Option<FetchParams>) -> Result<WeatherResult> {
     let params = params.unwrap_or_default();
     let url = format!(
         "https://services.surfline.com/kbyg/spots/forecasts/weather?spotId={}&days={}&intervalHours={}",
         spot_id, params.days, params.interval_hours
     );
-    fetch(url.as_str()).await
+    fetch(source, url.as_str()).await
 }
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use crate::{http::fetch, surfline_types::common::FetchParams};
+use crate::{
+    http::{DataSource, fetch},
+    surfline_types::common::FetchParams,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WindResult {
@@ -31,11 +34,11 @@ pub struct WindMeasurement {
     pub speed: f32,
 }
 
-pub async fn fetch_wind(spot_id: &str, params: Option<FetchParams>) -> Result<WindResult> {
+pub async fn fetch_wind(source: &DataSource, spot_id: &str, params: Option<FetchParams>) -> Result<WindResult> {
     let params = params.unwrap_or_default();
     let url = format!(
         "https://services.surfline.com/kbyg/spots/forecasts/wind?spotId={}&days={}&intervalHours={}",
         spot_id, params.days, params.interval_hours
     );
-    fetch(url.as_str()).await
+    fetch(source, url.as_str()).await
 }
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::Path,
+};
+
+use crate::publish::{PublishConfig, PublishTarget};
+
+// hex characters of the content hash in a versioned key
+const VERSION_CHARS: usize = 16;
+
+/// How long a version nothing points at is kept, devices reload their config hourly with the screen saver
+pub const VERSION_GRACE_PERIOD: TimeDelta = TimeDelta::hours(24);
+
+/// `key` and the extension it ends in, `kitchen/report.qoi` -> (`kitchen/report`, `.qoi`)
+fn split_extension(key: &str) -> (&str, &str) {
+    match key.rfind('.') {
+        Some(dot) if !key[dot..].contains('/') => key.split_at(dot),
+        _ => (key, ""),
+    }
+}
+
+/// Content-addressed key of an image published for `key`, `report.qoi` -> `report.<hash prefix>.qoi`
+pub fn versioned_key(key: &str, hash: &str) -> String {
+    let (stem, extension) = split_extension(key);
+    format!("{}.{}{}", stem, &hash[..VERSION_CHARS.min(hash.len())], extension)
+}
+
+fn is_version_of(key: &str, version_key: &str) -> bool {
+    let (stem, extension) = split_extension(key);
+    version_key
+        .strip_prefix(stem)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .and_then(|rest| rest.strip_suffix(extension))
+        .is_some_and(|version| version.len() == VERSION_CHARS && version.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Versions of published screens that no config points at anymore by URL, with the time that was first noticed.
+/// Kept in the state file, so a restart or a `--once` run picks up the grace periods where the last run left them
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetiredVersions {
+    since: HashMap<String, DateTime<Utc>>,
+    // screen URLs by device config key, so the versions of a screen removed from its config are still deleted
+    #[serde(default)]
+    screens: HashMap<String, BTreeSet<String>>,
+}
+
+impl RetiredVersions {
+    /// The versions saved at `path`, none when there is no file yet
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).with_context(|| format!("Failed to parse {:?}", path)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RetiredVersions::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {:?}", path)),
+        }
+    }
+
+    /// Write the versions to `path` for the next run, next to it first so a crash never leaves half a file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut partial_path = path.to_path_buf().into_os_string();
+        partial_path.push(".partial");
+        std::fs::write(&partial_path, serde_json::to_vec(self)?)
+            .with_context(|| format!("Failed to write {:?}", partial_path))?;
+        std::fs::rename(&partial_path, path).with_context(|| format!("Failed to write {:?}", path))
+    }
+
+    /// Screen URLs to collect the versions of for the device config `device`: the `configured` ones, whatever their
+    /// schedule, and the ones it configured in earlier runs until their last version is deleted
+    pub fn screens_of<'a>(&mut self, device: &str, configured: impl Iterator<Item = &'a str>) -> Vec<String> {
+        let screens = self.screens.entry(device.to_string()).or_default();
+        screens.extend(configured.map(str::to_string));
+        screens.iter().cloned().collect()
+    }
+
+    /// Delete the versions of the screen configured at `screen_url` that aren't in `live_urls` and haven't been for
+    /// `VERSION_GRACE_PERIOD`, returns the deleted URLs
+    pub async fn collect<T: PublishTarget>(
+        &mut self,
+        publish: &PublishConfig,
+        target: &T,
+        screen_url: &str,
+        live_urls: &HashSet<String>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<String>> {
+        let key = publish.key(screen_url)?;
+        let (stem, _) = split_extension(&key);
+        let mut deleted = Vec::new();
+        let mut remaining = 0;
+        for version_key in target.list(&format!("{}.", stem)).await? {
+            let url = format!("{}{}", publish.public_url(), version_key);
+            if !is_version_of(&key, &version_key) {
+                continue;
+            }
+            if live_urls.contains(&url) {
+                self.since.remove(&url);
+                remaining += 1;
+                continue;
+            }
+            let retired_at = *self.since.entry(url.clone()).or_insert(now);
+            if now - retired_at >= VERSION_GRACE_PERIOD {
+                target.delete(&version_key).await?;
+                self.since.remove(&url);
+                deleted.push(url);
+            } else {
+                remaining += 1;
+            }
+        }
+        // nothing left to delete, a screen that's still configured is picked up again by `screens_of`
+        if remaining == 0 {
+            self.screens.values_mut().for_each(|screens| {
+                screens.remove(screen_url);
+            });
+        }
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Warmest suit recommended from `min_temp` (°F) upwards
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WetsuitRule {
+    pub min_temp: f32,
+    pub suit: String,
+}
+
+/// Water temperature to wetsuit table, configurable per screen with the `wetsuits` param
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WetsuitTable(pub Vec<WetsuitRule>);
+
+impl Default for WetsuitTable {
+    fn default() -> Self {
+        let rule = |min_temp: f32, suit: &str| WetsuitRule {
+            min_temp,
+            suit: suit.into(),
+        };
+        WetsuitTable(vec![
+            rule(72., "Boardshorts"),
+            rule(65., "2mm spring"),
+            rule(60., "3/2mm"),
+            rule(55., "4/3mm"),
+            rule(50., "5/4mm+boots"),
+            rule(f32::MIN, "6/5mm+hood"),
+        ])
+    }
+}
+
+impl WetsuitTable {
+    /// Suit of the rule with the highest `min_temp` at or below `temp_fahrenheit`
+    pub fn recommend(&self, temp_fahrenheit: f32) -> Option<&str> {
+        self.0
+            .iter()
+            .filter(|rule| temp_fahrenheit >= rule.min_temp)
+            .max_by(|a, b| a.min_temp.total_cmp(&b.min_temp))
+            .map(|rule| rule.suit.as_str())
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use surfboard_scraper::astronomy::{DayAstronomy, MoonPhaseName, moon_phase, moon_times, sun_times};
+
+const SANTA_CRUZ: (f64, f64) = (36.96, -122.02);
+const LONDON: (f64, f64) = (51.5, -0.13);
+const TROMSO: (f64, f64) = (69.65, 18.96);
+
+fn assert_close(actual: Option<DateTime<Utc>>, expected: DateTime<Utc>, minutes: i64) {
+    let actual = actual.expect("Missing time");
+    assert!(
+        (actual - expected).num_minutes().abs() <= minutes,
+        "{} is not within {} minutes of {}",
+        actual,
+        minutes,
+        expected
+    );
+}
+
+#[test]
+fn test_sun_times() {
+    let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+    let sun = sun_times(date, SANTA_CRUZ.0, SANTA_CRUZ.1);
+    assert_close(sun.sunrise, Utc.with_ymd_and_hms(2024, 6, 21, 12, 48, 0).unwrap(), 3);
+    assert_close(sun.sunset, Utc.with_ymd_and_hms(2024, 6, 22, 3, 32, 0).unwrap(), 3);
+    assert!(sun.civil_dawn.unwrap() < sun.sunrise.unwrap());
+    assert!(sun.civil_dusk.unwrap() > sun.sunset.unwrap());
+
+    let date = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+    let sun = sun_times(date, LONDON.0, LONDON.1);
+    assert_close(sun.sunrise, Utc.with_ymd_and_hms(2024, 12, 21, 8, 4, 0).unwrap(), 3);
+    assert_close(sun.sunset, Utc.with_ymd_and_hms(2024, 12, 21, 15, 54, 0).unwrap(), 3);
+}
+
+#[test]
+fn test_polar_day_and_night() {
+    let winter = sun_times(NaiveDate::from_ymd_opt(2024, 12, 21).unwrap(), TROMSO.0, TROMSO.1);
+    assert_eq!((winter.sunrise, winter.sunset), (None, None));
+    assert!(winter.civil_dawn.is_some());
+
+    let summer = sun_times(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap(), TROMSO.0, TROMSO.1);
+    assert_eq!((summer.sunrise, summer.sunset), (None, None));
+}
+
+#[test]
+fn test_moon_phase() {
+    let full = moon_phase(Utc.with_ymd_and_hms(2024, 1, 25, 17, 54, 0).unwrap());
+    assert!(full.illumination > 0.99);
+    assert_eq!(full.name(), MoonPhaseName::FullMoon);
+    assert!(full.is_spring_tide());
+
+    let new = moon_phase(Utc.with_ymd_and_hms(2024, 1, 11, 11, 57, 0).unwrap());
+    assert!(new.illumination < 0.01);
+    assert_eq!(new.name(), MoonPhaseName::NewMoon);
+
+    let first_quarter = moon_phase(Utc.with_ymd_and_hms(2024, 1, 18, 3, 53, 0).unwrap());
+    assert!((first_quarter.illumination - 0.5).abs() < 0.05);
+    assert_eq!(first_quarter.name(), MoonPhaseName::FirstQuarter);
+    assert!(!first_quarter.is_spring_tide());
+
+    let last_quarter = moon_phase(Utc.with_ymd_and_hms(2024, 2, 2, 23, 18, 0).unwrap());
+    assert_eq!(last_quarter.name(), MoonPhaseName::LastQuarter);
+}
+
+#[test]
+fn test_moon_times() {
+    // a full moon rises around sunset and sets around sunrise
+    let start = Utc.with_ymd_and_hms(2024, 1, 25, 8, 0, 0).unwrap();
+    let moon = moon_times(start, SANTA_CRUZ.0, SANTA_CRUZ.1);
+    let sun = sun_times(NaiveDate::from_ymd_opt(2024, 1, 25).unwrap(), SANTA_CRUZ.0, SANTA_CRUZ.1);
+    assert_close(moon.rise, sun.sunset.unwrap(), 60);
+    assert_close(moon.set, sun.sunrise.unwrap(), 60);
+
+    let day = DayAstronomy::new(NaiveDate::from_ymd_opt(2024, 1, 25).unwrap(), -8, SANTA_CRUZ.0, SANTA_CRUZ.1);
+    assert_eq!(day.moon, moon);
+    assert_eq!(day.moon_phase.name(), MoonPhaseName::FullMoon);
+}
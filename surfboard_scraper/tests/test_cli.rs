@@ -0,0 +1,140 @@
+use clap::Parser;
+use std::path::Path;
+
+use surfboard_scraper::{
+    cli::{Cli, Command, render, validate},
+    common::framebuffer::decode_epd,
+    logging::LogFormat,
+};
+
+#[test]
+fn test_cli_args() {
+    let cli = Cli::try_parse_from(["surfboard_scraper"]).unwrap();
+    assert_eq!(cli.config_directory, Path::new("deploy/configs"));
+    assert_eq!(cli.state_file, Path::new("scraper_state.json"));
+    assert!(cli.command.is_none());
+
+    let cli = Cli::try_parse_from([
+        "surfboard_scraper",
+        "publish",
+        "--once",
+        "--config-directory",
+        "configs",
+    ])
+    .unwrap();
+    assert_eq!(cli.config_directory, Path::new("configs"));
+    match cli.command {
+        Some(Command::Publish(args)) => assert!(args.once && !args.dry_run),
+        command => panic!("Not a publish: {:?}", command),
+    }
+
+    let cli = Cli::try_parse_from([
+        "surfboard_scraper",
+        "daemon",
+        "--log-level",
+        "debug",
+        "--log-format",
+        "json",
+    ])
+    .unwrap();
+    assert!(matches!(cli.command, Some(Command::Daemon)));
+    assert_eq!(cli.log_level, "debug");
+    assert_eq!(cli.log_format, LogFormat::Json);
+    assert!(Cli::try_parse_from(["surfboard_scraper", "--log-format", "xml"]).is_err());
+    assert!(Cli::try_parse_from(["surfboard_scraper", "render", "--config", "0001.json"]).is_err());
+    assert!(Cli::try_parse_from(["surfboard_scraper", "publish", "--forever"]).is_err());
+}
+
+#[test]
+fn test_validate() {
+    assert_eq!(validate(Path::new("deploy/configs")).unwrap(), Vec::<String>::new());
+
+    let config_directory = std::env::temp_dir().join("surfboard_test_validate");
+    std::fs::create_dir_all(&config_directory).unwrap();
+    std::fs::write(config_directory.join("broken.json"), "{").unwrap();
+    // the fixtures have no publish target
+    std::fs::copy("tests/data/fixtures/screens.json", config_directory.join("0001.json")).unwrap();
+    let problems = validate(&config_directory).unwrap();
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].starts_with("0001.json: no \"publish\" target"));
+    assert!(problems[1].starts_with("broken.json: Failed to parse"));
+
+    assert_eq!(validate(&config_directory.join("empty")).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_render() {
+    let out_directory = std::env::temp_dir().join("surfboard_test_render");
+    std::fs::create_dir_all(&out_directory).unwrap();
+    for file in ["report.png", "report.qoi"] {
+        let out = out_directory.join(file);
+        let _ = std::fs::remove_file(&out);
+        let cli = Cli::try_parse_from([
+            "surfboard_scraper",
+            "render",
+            "--config",
+            "tests/data/fixtures/screens.json",
+            "--screen",
+            "surf-report-24h",
+            "--out",
+            &out.to_string_lossy(),
+            "--fixtures",
+            "tests/data/fixtures",
+            "--time",
+            "2024-08-10T13:00:00Z",
+        ])
+        .unwrap();
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("Not a render: {:?}", cli.command);
+        };
+        render(&cli.config_directory, &args).await.unwrap();
+        assert!(std::fs::metadata(&out).unwrap().len() > 0);
+
+        let args = surfboard_scraper::cli::RenderArgs {
+            screen: "missing".to_string(),
+            ..args
+        };
+        assert!(render(&cli.config_directory, &args).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_render_epd() {
+    let out_directory = std::env::temp_dir().join("surfboard_test_render_epd");
+    std::fs::create_dir_all(&out_directory).unwrap();
+    let config = out_directory.join("epd.json");
+    let screen = serde_json::json!({
+        "id": "message",
+        "key": "Message",
+        "params": { "title": "Packed", "message": "Rendered as the device gets it" },
+        "url": "https://example.com/message.epd",
+        "format": "epd2"
+    });
+    std::fs::write(
+        &config,
+        serde_json::json!({ "screens": [screen], "screen_saver": null }).to_string(),
+    )
+    .unwrap();
+
+    // the screen's format wins over the file's extension, as it does when publishing
+    for file in ["message.epd", "message.qoi"] {
+        let out = out_directory.join(file);
+        let cli = Cli::try_parse_from([
+            "surfboard_scraper",
+            "render",
+            "--config",
+            &config.to_string_lossy(),
+            "--screen",
+            "message",
+            "--out",
+            &out.to_string_lossy(),
+        ])
+        .unwrap();
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("Not a render: {:?}", cli.command);
+        };
+        render(&cli.config_directory, &args).await.unwrap();
+        let (width, height, planes) = decode_epd(&std::fs::read(&out).unwrap()).unwrap();
+        assert_eq!((width, height, planes.len()), (800, 480, 2));
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use std::fs;
 
 use surfboard_scraper::device_config::Configuration;
@@ -18,3 +19,51 @@ async fn test_config_parsing() {
         let _data = serde_json::from_str::<Configuration>(contents.as_str()).unwrap();
     }
 }
+
+#[test]
+fn test_scheduled_screens() {
+    let config: Configuration = serde_json::from_str(
+        r#"{
+            "screens": [
+                { "id": "always", "key": "Message", "params": { "message": "always" }, "url": "https://example.com/always.qoi" },
+                {
+                    "id": "trip", "key": "Message", "params": { "message": "trip" }, "url": "https://example.com/trip.qoi",
+                    "active_from": "2025-06-01", "active_until": "2025-06-20"
+                },
+                {
+                    "id": "weekend", "key": "Message", "params": { "message": "weekend" }, "url": "https://example.com/weekend.qoi",
+                    "recurring": { "weekly": ["Sat", "Sun"] }
+                }
+            ],
+            "screen_saver": { "id": "default", "key": "Message", "params": { "message": "default" }, "url": "https://example.com/saver.qoi" },
+            "scheduled_screen_savers": [
+                {
+                    "id": "bday", "key": "Message", "params": { "message": "bday" }, "url": "https://example.com/saver.qoi",
+                    "recurring": { "yearly": { "month": 6, "day": 14 } }
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let screen_ids = |config: &Configuration| config.screens.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+    let all_ids: Vec<&str> = config.all_screens().map(|s| s.id.as_str()).collect();
+    assert_eq!(all_ids, vec!["always", "trip", "weekend", "default", "bday"]);
+
+    // Saturday, inside the trip window and on the birthday
+    let active = config.active_on(NaiveDate::from_ymd_opt(2025, 6, 14).unwrap());
+    assert_eq!(screen_ids(&active), vec!["always", "trip", "weekend"]);
+    assert_eq!(active.screen_saver.unwrap().id, "bday");
+    assert!(active.scheduled_screen_savers.is_empty());
+
+    // Monday after the trip
+    let active = config.active_on(NaiveDate::from_ymd_opt(2025, 6, 23).unwrap());
+    assert_eq!(screen_ids(&active), vec!["always"]);
+    let all_ids: Vec<&str> = active.all_screens().map(|s| s.id.as_str()).collect();
+    assert_eq!(all_ids, vec!["always", "default"]);
+    assert_eq!(active.screen_saver.unwrap().id, "default");
+
+    // schedules are not published to devices
+    let published = serde_json::to_string(&config.active_on(NaiveDate::from_ymd_opt(2025, 6, 2).unwrap())).unwrap();
+    assert!(!published.contains("active_from") && !published.contains("scheduled_screen_savers"));
+}
@@ -1,7 +1,15 @@
-use std::io::Cursor;
+use chrono::{TimeZone, Utc};
+use embedded_graphics::prelude::*;
+use embedded_graphics_simulator::SimulatorDisplay;
+use epd_waveshare::color::TriColor;
+use std::{collections::HashMap, io::Cursor};
 use tokio::fs;
 
-use surfboard_scraper::device_config::Configuration;
+use surfboard_scraper::device_config::{Configuration, ScreenConfiguration};
+use surfboard_scraper::http::DataSource;
+use surfboard_scraper::screen::{RenderContext, SERVER_SIDE_IMAGE_BYTES, Screen};
+use surfboard_scraper::surf_report_24h::data::SurfReport24HData;
+use surfboard_scraper::surf_report_week::data::SurfReportWeekData;
 
 #[tokio::test]
 async fn test_draw_screns() {
@@ -12,16 +20,20 @@ async fn test_draw_screns() {
             .as_str(),
     )
     .expect("Failed to parse configuration");
+    let context = config.render_context();
 
     for screen in config.screens {
         screen
-            .draw_to_png(&format!("tests/data/{}.png", screen.id))
+            .draw_to_png(&context, &format!("{}/{}.png", env!("CARGO_TARGET_TMPDIR"), screen.id))
             .await
             .expect("Failed to draw image");
     }
     if let Some(screen_saver) = config.screen_saver {
         screen_saver
-            .draw_to_png(&format!("tests/data/{}.png", screen_saver.id))
+            .draw_to_png(
+                &context,
+                &format!("{}/{}.png", env!("CARGO_TARGET_TMPDIR"), screen_saver.id),
+            )
             .await
             .expect("Failed to draw image");
     }
@@ -36,11 +48,12 @@ async fn test_draw_screns_qoi() {
             .as_str(),
     )
     .expect("Failed to parse configuration");
+    let context = config.render_context();
 
     for screen in config.screens {
         let mut bytes: Vec<u8> = Vec::new();
         screen
-            .draw_to_qoi(&mut Cursor::new(&mut bytes))
+            .draw_to_qoi(&context, &mut Cursor::new(&mut bytes))
             .await
             .expect("Failed to draw qoi");
         assert!(bytes.len() < 1024 * 24)
@@ -48,9 +61,123 @@ async fn test_draw_screns_qoi() {
     if let Some(screen_saver) = config.screen_saver {
         let mut bytes: Vec<u8> = Vec::new();
         screen_saver
-            .draw_to_qoi(&mut Cursor::new(&mut bytes))
+            .draw_to_qoi(&context, &mut Cursor::new(&mut bytes))
             .await
             .expect("Failed to draw qoi");
         assert!(bytes.len() < 1024 * 24)
     }
 }
+
+#[tokio::test]
+async fn test_draw_message_qoi() {
+    let screen: ScreenConfiguration = serde_json::from_str(
+        r#"{
+            "id": "message",
+            "key": "Message",
+            "params": {
+                "title": "Happy Birthday!",
+                "message": "A message long enough that it has to be wrapped across lines, including a hyperextraordinarilylongwordthatcannotbebrokenonwhitespaceatallanywhere",
+                "title_size": "huge",
+                "body_size": "large",
+                "image": "deploy/photo.png",
+                "border": true
+            },
+            "url": "https://example.com/message.qoi"
+        }"#,
+    )
+    .expect("Failed to parse screen configuration");
+
+    let mut bytes: Vec<u8> = Vec::new();
+    screen
+        .draw_to_qoi(&RenderContext::default(), &mut Cursor::new(&mut bytes))
+        .await
+        .expect("Failed to draw qoi");
+    assert!(bytes.len() < 1024 * 24)
+}
+
+#[tokio::test]
+async fn test_draw_photo_qoi() {
+    // synthetic "photo" with smooth gradients and texture, the worst case for dithered image size
+    let photo_dir = std::env::temp_dir().join("surfboard_test_photos");
+    std::fs::create_dir_all(&photo_dir).expect("Failed to create photo directory");
+    let photo = image::RgbImage::from_fn(1200, 900, |x, y| {
+        let texture = ((x * 7 + y * 13) % 23) as u8;
+        image::Rgb([(x / 5) as u8, (y / 4) as u8 + texture, 255 - (x / 5) as u8])
+    });
+    photo
+        .save(photo_dir.join("gradient.jpg"))
+        .expect("Failed to save photo");
+
+    let screen: ScreenConfiguration = serde_json::from_value(serde_json::json!({
+        "id": "photo",
+        "key": "Photo",
+        "params": { "path": photo_dir.to_str().unwrap() },
+        "url": "https://example.com/photo.qoi"
+    }))
+    .expect("Failed to parse screen configuration");
+
+    let mut bytes: Vec<u8> = Vec::new();
+    screen
+        .draw_to_qoi(&RenderContext::default(), &mut Cursor::new(&mut bytes))
+        .await
+        .expect("Failed to draw qoi");
+    assert!(bytes.len() <= SERVER_SIDE_IMAGE_BYTES)
+}
+
+#[tokio::test]
+async fn test_draw_message_qr_code() {
+    let mut screen: ScreenConfiguration = serde_json::from_value(serde_json::json!({
+        "id": "guest-wifi",
+        "key": "Message",
+        "params": {
+            "title": "Guest Wi-Fi",
+            "message": "Scan to join",
+            "qr_code": "WIFI:T:WPA;S:surfshack;P:hang-ten-2024;;"
+        },
+        "url": "https://example.com/guest-wifi.qoi"
+    }))
+    .expect("Failed to parse screen configuration");
+
+    let display = screen
+        .draw_to_display(&RenderContext::default())
+        .await
+        .expect("Failed to draw qr code");
+    let dark_pixels = display
+        .bounding_box()
+        .points()
+        .filter(|p| display.get_pixel(*p) == TriColor::Black)
+        .count();
+    assert!(dark_pixels > 5_000);
+
+    // too long to fit in any QR code version
+    screen.params.insert("qr_code".into(), "x".repeat(8000).into());
+    assert!(screen.draw_to_display(&RenderContext::default()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_draw_surf_report_qr_code_too_long() {
+    let context = RenderContext {
+        data_source: DataSource::Fixtures("tests/data/fixtures".into()),
+        frozen_time: Some(Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap()),
+        ..RenderContext::default()
+    };
+    let params: HashMap<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({ "spot_id": "5842041f4e65fad6a7708807", "qr_code": true }))
+            .expect("Failed to parse params");
+    let too_long = Some("x".repeat(8000));
+
+    // the report is drawn without its QR code rather than failing or panicking
+    let mut report_24h = SurfReport24HData::from_params(&SurfReport24HData::parse_params(&params).unwrap(), &context)
+        .await
+        .expect("Failed to load 24h report");
+    report_24h.qr_code = too_long.clone();
+    let mut display = SimulatorDisplay::<TriColor>::new(Size::new(800, 480));
+    report_24h.draw(&mut display).expect("Failed to draw 24h report");
+
+    let mut report_week =
+        SurfReportWeekData::from_params(&SurfReportWeekData::parse_params(&params).unwrap(), &context)
+            .await
+            .expect("Failed to load week report");
+    report_week.qr_code = too_long;
+    report_week.draw(&mut display).expect("Failed to draw week report");
+}
@@ -0,0 +1,85 @@
+use std::io::Cursor;
+
+use embedded_graphics::prelude::*;
+
+use surfboard_scraper::common::framebuffer::{decode_display, decode_epd, packbits, unpackbits};
+use surfboard_scraper::device_config::ScreenConfiguration;
+use surfboard_scraper::screen::{OutputFormat, RenderContext};
+
+#[test]
+fn test_packbits_roundtrip() {
+    let cases: Vec<Vec<u8>> = vec![
+        vec![],
+        vec![7],
+        vec![0; 1000],
+        vec![1, 2, 2, 3, 3, 3, 4, 4, 4, 4],
+        (0..1000).map(|i| (i * 31 % 251) as u8).collect(),
+        (0..1000).map(|i| if i % 300 < 150 { 0xff } else { (i % 7) as u8 }).collect(),
+    ];
+    for data in cases {
+        let packed = packbits(&data);
+        assert_eq!(unpackbits(&packed).expect("Failed to unpack"), data);
+    }
+    assert_eq!(packbits(&[0; 48000]).len(), 48000 / 128 * 2);
+}
+
+#[tokio::test]
+async fn test_epd_matches_qoi() {
+    let mut screen: ScreenConfiguration = serde_json::from_value(serde_json::json!({
+        "id": "message",
+        "key": "Message",
+        "params": {
+            "title": "Packed",
+            "message": "Framebuffer straight into the panel",
+            "border": true
+        },
+        "url": "https://example.com/message.epd",
+        "format": "epd2"
+    }))
+    .expect("Failed to parse screen configuration");
+    assert_eq!(screen.format, OutputFormat::Epd2);
+
+    let mut epd: Vec<u8> = Vec::new();
+    screen.render(&RenderContext::default(), &mut Cursor::new(&mut epd)).await.expect("Failed to render epd");
+    let (width, height, planes) = decode_epd(&epd).expect("Failed to decode epd");
+    assert_eq!((width, height, planes.len()), (800, 480, 2));
+
+    screen.format = OutputFormat::Qoi;
+    let mut qoi: Vec<u8> = Vec::new();
+    screen.render(&RenderContext::default(), &mut Cursor::new(&mut qoi)).await.expect("Failed to render qoi");
+    let image = image::load_from_memory(&qoi).expect("Failed to decode qoi").to_rgb8();
+
+    // the firmware treats everything at or below mid luminance as ink when decoding QOI
+    let bit = |plane: &[u8], x: u32, y: u32| plane[(y * 100 + x / 8) as usize] & (0x80 >> (x % 8)) != 0;
+    for (x, y, pixel) in image.enumerate_pixels() {
+        let [r, g, b] = pixel.0;
+        let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+        assert_eq!(bit(&planes[0], x, y), luminance <= 128.0, "ink at {},{}", x, y);
+        assert_eq!(bit(&planes[1], x, y), pixel.0 == [255, 0, 0], "chromatic at {},{}", x, y);
+    }
+    assert!(epd.len() < qoi.len());
+}
+
+#[tokio::test]
+async fn test_decode_display() {
+    let mut screen: ScreenConfiguration = serde_json::from_value(serde_json::json!({
+        "id": "message",
+        "key": "Message",
+        "params": { "title": "Decoded", "message": "Back from the publish target", "border": true },
+        "url": "https://example.com/message.epd",
+        "format": "epd2"
+    }))
+    .expect("Failed to parse screen configuration");
+    let display = screen.draw_to_display(&RenderContext::default()).await.expect("Failed to draw");
+
+    for format in [OutputFormat::Epd2, OutputFormat::Qoi] {
+        screen.format = format;
+        let decoded = decode_display(&screen.encode(&display).unwrap()).expect("Failed to decode");
+        assert_eq!(decoded.size(), display.size());
+        for point in display.bounding_box().points() {
+            assert_eq!(decoded.get_pixel(point), display.get_pixel(point), "{:?} at {}", format, point);
+        }
+    }
+    assert!(decode_display(b"epdf").is_err());
+    assert!(decode_display(b"nonsense").is_err());
+}
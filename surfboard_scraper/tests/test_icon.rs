@@ -0,0 +1,85 @@
+use embedded_graphics::image::{GetPixel, ImageRaw};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use image::{DynamicImage, Luma, Rgba, RgbaImage};
+use surfboard_scraper::common::icon::{IconBitmap, const_name};
+use surfboard_scraper::image_data;
+
+fn ink_count(icon: &IconBitmap) -> u32 {
+    icon.data.iter().map(|byte| byte.count_ones()).sum()
+}
+
+#[test]
+fn test_icons_match_image_data() {
+    // src/image_data.rs has to be regenerated (`make icons`) whenever tools/icons changes
+    let icons: [(&str, ImageRaw<BinaryColor>); 3] = [
+        ("weather_sunny", image_data::WEATHER_SUNNY),
+        ("mist", image_data::MIST),
+        ("wind", image_data::WIND),
+    ];
+    for (name, expected) in icons {
+        let image = image::open(format!("tools/icons/{}.png", name)).expect("Failed to open icon");
+        let icon = IconBitmap::from_image(&image, None, 128, false);
+        assert_eq!(icon.image_raw().size(), expected.size(), "{}", name);
+        for point in expected.bounding_box().points() {
+            assert_eq!(
+                icon.image_raw().pixel(point),
+                expected.pixel(point),
+                "{} at {}",
+                name,
+                point
+            );
+        }
+    }
+}
+
+#[test]
+fn test_icon_threshold_and_transparency() {
+    // left half grey, right half transparent black
+    let image = RgbaImage::from_fn(10, 4, |x, _| {
+        if x < 5 {
+            Rgba([100, 100, 100, 255])
+        } else {
+            Rgba([0, 0, 0, 0])
+        }
+    });
+    let image = DynamicImage::ImageRgba8(image);
+
+    let icon = IconBitmap::from_image(&image, None, 128, false);
+    assert_eq!((icon.width, icon.height), (10, 4));
+    assert_eq!(icon.data, [0b11111000, 0b00000000].repeat(4));
+
+    let icon = IconBitmap::from_image(&image, None, 64, false);
+    assert_eq!(ink_count(&icon), 0);
+}
+
+#[test]
+fn test_icon_resize_and_dither() {
+    let grey = DynamicImage::ImageLuma8(image::ImageBuffer::from_pixel(100, 50, Luma([128u8])));
+
+    // scaled to fit the square and centered vertically
+    let icon = IconBitmap::from_image(&grey, Some(48), 200, false);
+    assert_eq!((icon.width, icon.height), (48, 48));
+    assert_eq!(ink_count(&icon), 48 * 24);
+    assert_eq!(icon.data[..6 * 12], [0; 6 * 12]);
+
+    // mid grey dithers to about half ink
+    let icon = IconBitmap::from_image(&grey, Some(48), 200, true);
+    assert!((ink_count(&icon) as i32 - 48 * 12).abs() < 48);
+}
+
+#[test]
+fn test_icon_rust_const() {
+    let image = DynamicImage::ImageLuma8(image::ImageBuffer::from_fn(9, 2, |x, y| {
+        if x == y { Luma([0u8]) } else { Luma([255u8]) }
+    }));
+    let icon = IconBitmap::from_image(&image, None, 128, false);
+    assert_eq!(
+        icon.to_rust_const(&const_name("partly-cloudy")),
+        "#[rustfmt::skip]\n\
+         pub const PARTLY_CLOUDY: ImageRaw<BinaryColor> = ImageRaw::new(&[\n\
+         \t0b10000000, 0b00000000,\n\
+         \t0b01000000, 0b00000000,\n\
+         ], 9);\n"
+    );
+}
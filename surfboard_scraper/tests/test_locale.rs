@@ -0,0 +1,89 @@
+use chrono::{NaiveDate, Weekday};
+use embedded_graphics::mono_font::iso_8859_1::FONT_6X10;
+use surfboard_scraper::common::draw_utils::{format_wave_height, format_wind_speed};
+use surfboard_scraper::device_config::Configuration;
+use surfboard_scraper::locale::Locale;
+
+#[test]
+fn test_locale_configuration() {
+    let config: Configuration = serde_json::from_str(r#"{ "screens": [], "screen_saver": null }"#).unwrap();
+    assert_eq!(config.locale, Locale::En);
+
+    let config: Configuration =
+        serde_json::from_str(r#"{ "screens": [], "screen_saver": null, "locale": "de" }"#).unwrap();
+    assert_eq!(config.render_context().locale, Locale::De);
+    let date = NaiveDate::from_ymd_opt(2025, 6, 14).unwrap();
+    assert_eq!(config.active_on(date).locale, Locale::De);
+
+    assert!(serde_json::from_str::<Locale>(r#""xx""#).is_err());
+}
+
+#[test]
+fn test_locale_formatting() {
+    // the default keeps the English labels the screens have always had
+    let en = Locale::En.messages();
+    let updated = NaiveDate::from_ymd_opt(2024, 8, 3)
+        .unwrap()
+        .and_hms_opt(9, 5, 0)
+        .unwrap();
+    assert_eq!(en.format_updated(&updated), "Updated: 8/3 09:05");
+    assert_eq!(en.format_time(6, 58), "6:58");
+    assert_eq!(en.format_hour_range(6, 9), "06-09");
+    assert_eq!(format_wave_height(en, 2, 4), "2-4ft");
+    assert_eq!(format_wind_speed(en, 12.4), "12kt");
+
+    let us = Locale::EnUs.messages();
+    assert_eq!(us.format_updated(&updated), "Updated: 8/3 9:05am");
+    assert_eq!(us.format_time(0, 30), "12:30am");
+    assert_eq!(us.format_time(12, 0), "12:00pm");
+    assert_eq!(us.format_hour(18), "6pm");
+    assert_eq!(us.format_hour_range(6, 9), "6am-9am");
+
+    assert_eq!(Locale::EnGb.messages().format_date(updated.date()), "3/8");
+    let de = Locale::De.messages();
+    assert_eq!(de.format_updated(&updated), "Aktualisiert: 3.8. 09:05");
+    assert_eq!(de.weekday(Weekday::Sun), "So");
+    assert_eq!(format_wind_speed(Locale::Fr.messages(), 8.), "8nd");
+}
+
+#[test]
+fn test_locale_characters_in_font() {
+    // every label has to exist in the ISO 8859-1 fonts the screens draw with
+    let locales = [
+        Locale::En,
+        Locale::EnUs,
+        Locale::EnGb,
+        Locale::Es,
+        Locale::Fr,
+        Locale::De,
+        Locale::Pt,
+    ];
+    for locale in locales {
+        let messages = locale.messages();
+        let labels = [
+            messages.updated,
+            messages.water,
+            messages.sunrise,
+            messages.sunset,
+            messages.moonrise,
+            messages.best_session,
+            messages.second_session,
+            messages.spring_tide,
+            messages.high_tide,
+            messages.low_tide,
+            messages.feet,
+            messages.knots,
+        ];
+        for label in labels.iter().chain(messages.weekdays.iter()) {
+            for c in label.chars() {
+                assert!(
+                    FONT_6X10.glyph_mapping.index(c) != FONT_6X10.glyph_mapping.index('?') || c == '?',
+                    "{:?} in {:?} ({:?})",
+                    c,
+                    label,
+                    locale
+                );
+            }
+        }
+    }
+}
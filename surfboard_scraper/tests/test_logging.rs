@@ -0,0 +1,73 @@
+use std::{
+    io::Write,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+use surfboard_scraper::device_config::ScreenConfiguration;
+use surfboard_scraper::logging::{LogFormat, device_span, init, screen_span, subscriber};
+
+/// Everything a subscriber writes, shared with the test
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Captured {
+    type Writer = Captured;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[test]
+fn test_logging() {
+    init("info", LogFormat::Json).unwrap();
+    tracing::info!(device = "0001.json", screen = "surf-report-24h", "Uploaded");
+    // the subscriber is global, set once per process
+    assert!(init("info", LogFormat::Text).is_err());
+}
+
+#[test]
+fn test_json_spans() {
+    let screen: ScreenConfiguration = serde_json::from_value(serde_json::json!({
+        "id": "surf-report-24h",
+        "key": "SurfReport24h",
+        "params": { "spot_id": "5842041f4e65fad6a7708807" },
+        "url": "https://example.com/surf-report-24h.qoi"
+    }))
+    .unwrap();
+    let captured = Captured::default();
+    tracing::subscriber::with_default(subscriber("info", LogFormat::Json, captured.clone()).unwrap(), || {
+        let _device = device_span(Path::new("deploy/configs/0001.json")).entered();
+        let _screen = screen_span(&screen).entered();
+        tracing::info!(bytes = 1234, "Uploaded");
+        tracing::debug!("Filtered out");
+    });
+
+    let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<serde_json::Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 1, "{}", output);
+    let event = &lines[0];
+    assert_eq!(event["message"], "Uploaded");
+    assert_eq!(event["bytes"], 1234);
+    // every line carries the device and screen it's about
+    let spans = event["spans"].as_array().unwrap();
+    assert_eq!(spans[0]["name"], "device");
+    assert_eq!(spans[0]["config"], "deploy/configs/0001.json");
+    assert_eq!(spans[1]["name"], "screen");
+    assert_eq!(spans[1]["id"], "surf-report-24h");
+    assert_eq!(spans[1]["url"], "https://example.com/surf-report-24h.qoi");
+    assert_eq!(spans[1]["spot_id"], "5842041f4e65fad6a7708807");
+}
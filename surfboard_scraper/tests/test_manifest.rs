@@ -0,0 +1,89 @@
+use chrono::{TimeDelta, TimeZone, Utc};
+use std::collections::HashMap;
+
+use surfboard_scraper::device_config::Configuration;
+use surfboard_scraper::manifest::{Manifest, ScreenManifest, manifest_key};
+use surfboard_scraper::publish::{DirectoryTarget, PublishTarget};
+use surfboard_scraper::versions::versioned_key;
+
+fn config() -> Configuration {
+    serde_json::from_str(&std::fs::read_to_string("deploy/configs/0001.json").unwrap()).unwrap()
+}
+
+#[test]
+fn test_manifest() {
+    let config = config();
+    let rendered_at = Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap();
+    let next_cycle = Some(rendered_at + TimeDelta::hours(3));
+
+    let fresh = ScreenManifest::new(&config.screens[0], "fresh".into(), rendered_at, rendered_at, next_cycle);
+    assert_eq!(fresh.valid_until, rendered_at + TimeDelta::hours(6));
+
+    // the screen saver failed an hour ago and was left up until the next cycle
+    let screen_saver = config.screen_saver.as_ref().unwrap();
+    let stale_since = rendered_at - TimeDelta::hours(7);
+    let mut stale = ScreenManifest::new(screen_saver, "stale".into(), stale_since, stale_since, next_cycle);
+    stale.next_refresh_at = Some(rendered_at - TimeDelta::hours(1));
+
+    let published = HashMap::from([(fresh.url.clone(), fresh.clone()), (stale.url.clone(), stale.clone())]);
+    let manifest = Manifest::new(&config, &published);
+    // screens that never published are left out
+    assert_eq!(manifest.screens, vec![fresh]);
+    assert_eq!(manifest.screen_saver, Some(stale.clone()));
+    assert_eq!(manifest.next_refresh_at, stale.next_refresh_at);
+    assert!(manifest.screen_saver.unwrap().valid_until < rendered_at);
+
+    // devices read Unix seconds
+    let json = serde_json::to_value(Manifest::new(&config, &published)).unwrap();
+    assert_eq!(json["screens"][0]["rendered_at"], rendered_at.timestamp());
+    assert_eq!(json["screens"][0]["hash"], "fresh");
+    assert_eq!(json["next_refresh_at"], stale.next_refresh_at.unwrap().timestamp());
+}
+
+#[test]
+fn test_empty_manifest() {
+    let manifest = Manifest::new(&config(), &HashMap::new());
+    assert!(manifest.screens.is_empty());
+    assert_eq!(manifest.next_refresh_at, None);
+    assert_eq!(
+        serde_json::to_string(&manifest).unwrap(),
+        r#"{"screens":[],"screen_saver":null,"next_refresh_at":null}"#
+    );
+}
+
+#[test]
+fn test_manifest_key() {
+    assert_eq!(manifest_key("0001.json"), "0001.manifest.json");
+    assert_eq!(manifest_key("devices/kitchen"), "devices/kitchen.manifest.json");
+}
+
+#[tokio::test]
+async fn test_published_from_manifest() {
+    let config = config();
+    let publish = config.publish.clone().unwrap();
+    let rendered_at = Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap();
+    let hash = "0123456789abcdef0123456789abcdef".to_string();
+    let entry = ScreenManifest {
+        url: format!(
+            "{}{}",
+            publish.public_url(),
+            versioned_key(&publish.key(&config.screens[0].url).unwrap(), &hash)
+        ),
+        ..ScreenManifest::new(&config.screens[0], hash, rendered_at, rendered_at, None)
+    };
+    let published = HashMap::from([(config.screens[0].url.clone(), entry)]);
+
+    let path = std::env::temp_dir().join("surfboard_test_published_manifest");
+    let _ = std::fs::remove_dir_all(&path);
+    let target = DirectoryTarget { path };
+    assert_eq!(Manifest::fetch(&target, "0001.manifest.json").await.unwrap(), None);
+    let manifest = serde_json::to_vec(&Manifest::new(&config, &published)).unwrap();
+    target.put("0001.manifest.json", manifest).await.unwrap();
+
+    // a restarted scraper finds the versions by the screens' configured URLs
+    let manifest = Manifest::fetch(&target, "0001.manifest.json").await.unwrap().unwrap();
+    assert_eq!(manifest.published(&publish, &config), published);
+
+    target.put("0001.manifest.json", b"{".to_vec()).await.unwrap();
+    assert!(Manifest::fetch(&target, "0001.manifest.json").await.is_err());
+}
@@ -0,0 +1,50 @@
+use chrono::{TimeZone, Utc};
+use std::time::Duration;
+
+use surfboard_scraper::{
+    http::{DataSource, fetch},
+    metrics::{METRICS, router, serve_or_log},
+};
+
+#[tokio::test]
+async fn test_metrics() {
+    let source = DataSource::Fixtures("tests/data/fixtures".into());
+    let url = "https://services.surfline.com/kbyg/spots/forecasts/wave?spotId=5842041f4e65fad6a7708807&days=2";
+    fetch::<serde_json::Value>(&source, url).await.unwrap();
+    let url = "https://services.surfline.com/kbyg/spots/forecasts/swell?spotId=5842041f4e65fad6a7708807&days=2";
+    assert!(fetch::<serde_json::Value>(&source, url).await.is_err());
+
+    let screen_url = "https://example.com/kitchen/report.qoi";
+    METRICS.rendered(screen_url, Duration::from_millis(250), 1234);
+    METRICS.published(screen_url, Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router()).await.unwrap() });
+    let response = reqwest::get(format!("http://{}/metrics", address)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let text = response.text().await.unwrap();
+    for line in [
+        "surfboard_fetch_duration_seconds_count{endpoint=\"wave\"} 1",
+        "surfboard_fetch_errors_total{endpoint=\"swell\"} 1",
+        "surfboard_render_duration_seconds_count{url=\"https://example.com/kitchen/report.qoi\"} 1",
+        "surfboard_encoded_bytes{url=\"https://example.com/kitchen/report.qoi\"} 1234",
+        "surfboard_last_publish_timestamp_seconds{url=\"https://example.com/kitchen/report.qoi\"} 1723294800",
+    ] {
+        assert!(text.lines().any(|metric| metric == line), "{} not in\n{}", line, text);
+    }
+    assert!(!text.contains("surfboard_fetch_errors_total{endpoint=\"wave\"}"));
+}
+
+#[tokio::test]
+async fn test_metrics_port_taken() {
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    assert!(surfboard_scraper::metrics::serve(port).await.is_err());
+    // the error is only logged, so the publishing it runs next to carries on
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), serve_or_log(port))
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,83 @@
+use chrono::{TimeZone, Utc};
+use clap::Parser;
+use std::net::SocketAddr;
+
+use surfboard_scraper::{
+    cli::{Cli, Command},
+    preview::{PreviewOptions, content_version, router},
+};
+
+async fn start_preview(options: PreviewOptions) -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router(options)).await.unwrap() });
+    address
+}
+
+#[tokio::test]
+async fn test_preview_server() {
+    let config_directory = std::env::temp_dir().join("surfboard_test_preview");
+    std::fs::create_dir_all(&config_directory).unwrap();
+    let config_path = config_directory.join("0001.json");
+    std::fs::copy("tests/data/fixtures/screens.json", &config_path).unwrap();
+
+    let options = PreviewOptions {
+        config_directory: config_directory.clone(),
+        address: "127.0.0.1:0".parse().unwrap(),
+        fixtures: Some("tests/data/fixtures".into()),
+        frozen_time: Some(Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap()),
+    };
+    let version = content_version(&options);
+    let address = start_preview(options.clone()).await;
+    let get = |path: &str| reqwest::get(format!("http://{}{}", address, path));
+
+    let index = get("/").await.unwrap().text().await.unwrap();
+    assert!(index.contains("href=\"/device/0001\""));
+    let device = get("/device/0001").await.unwrap().text().await.unwrap();
+    assert!(device.contains(&format!("/device/0001/screen/surf-report-week.png?v={}", version)));
+
+    let response = get("/device/0001/screen/surf-report-week.png").await.unwrap();
+    assert_eq!(response.headers()["content-type"], "image/png");
+    let png = image::load_from_memory(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!((png.width(), png.height()), (800, 480));
+
+    assert_eq!(get("/device/0001/screen/missing.png").await.unwrap().status(), 404);
+    assert_eq!(get("/device/0002").await.unwrap().status(), 404);
+
+    // pages reload once the version changes
+    assert_eq!(get("/version").await.unwrap().text().await.unwrap(), version);
+    std::fs::write(&config_path, std::fs::read_to_string(&config_path).unwrap() + "\n").unwrap();
+    assert_ne!(get("/version").await.unwrap().text().await.unwrap(), version);
+}
+
+#[test]
+fn test_preview_args() {
+    let preview = |args: &[&str]| match Cli::try_parse_from([&["surfboard_scraper", "preview"], args].concat()) {
+        Ok(Cli {
+            config_directory,
+            command: Some(Command::Preview(args)),
+            ..
+        }) => Ok(args.into_options(config_directory)),
+        Ok(cli) => panic!("Not a preview: {:?}", cli),
+        Err(e) => Err(e),
+    };
+    let options = preview(&[
+        "--port",
+        "9000",
+        "--fixtures",
+        "tests/data/fixtures",
+        "--time",
+        "2024-08-10T13:00:00Z",
+    ])
+    .unwrap();
+    assert_eq!(options.config_directory, std::path::Path::new("deploy/configs"));
+    assert_eq!(options.address.port(), 9000);
+    assert_eq!(options.fixtures, Some("tests/data/fixtures".into()));
+    assert_eq!(
+        options.frozen_time,
+        Some(Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap())
+    );
+
+    assert!(preview(&["--port"]).is_err());
+    assert!(preview(&["--watch"]).is_err());
+}
@@ -0,0 +1,146 @@
+use glob::glob;
+use std::fs;
+
+use surfboard_scraper::device_config::Configuration;
+use surfboard_scraper::publish::{
+    Connections, DirectoryTarget, PublishConfig, PublishTarget, Publisher, content_hash, put_if_changed,
+};
+use surfboard_scraper::versions::versioned_key;
+
+fn publish_config(json: &str) -> PublishConfig {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn test_publish_keys() {
+    let aws = publish_config(r#"{ "type": "aws_s3", "bucket": "surf" }"#);
+    assert_eq!(aws.public_url(), "https://surf.s3.us-east-1.amazonaws.com/");
+    assert_eq!(
+        aws.key("https://surf.s3.us-east-1.amazonaws.com/report.qoi").unwrap(),
+        "report.qoi"
+    );
+    assert!(aws.key("https://other.s3.us-east-1.amazonaws.com/report.qoi").is_err());
+    assert!(aws.key("https://surf.s3.us-east-1.amazonaws.com/").is_err());
+
+    let minio =
+        publish_config(r#"{ "type": "s3_compatible", "endpoint": "http://nas.local:9000/", "bucket": "surf" }"#);
+    assert_eq!(minio.public_url(), "http://nas.local:9000/surf/");
+    assert_eq!(
+        minio.key("http://nas.local:9000/surf/kitchen/report.qoi").unwrap(),
+        "kitchen/report.qoi"
+    );
+
+    let directory =
+        publish_config(r#"{ "type": "directory", "path": "/var/www/surf", "public_url": "http://nas.local/surf" }"#);
+    assert_eq!(directory.key("http://nas.local/surf/report.qoi").unwrap(), "report.qoi");
+}
+
+#[test]
+fn test_deploy_configs_publish() {
+    for entry in glob("deploy/configs/*.json").expect("Failed to read glob pattern") {
+        let path = entry.unwrap();
+        let config: Configuration = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let publish = config
+            .publish
+            .as_ref()
+            .unwrap_or_else(|| panic!("No publish target in {:?}", path));
+        for screen in config.screens.iter().chain(config.screen_saver.iter()) {
+            let key = publish
+                .key(&screen.url)
+                .unwrap_or_else(|_| panic!("{} isn't published by {:?}", screen.url, path));
+            // the firmware keeps screen URLs in a `String<128>`
+            let url = format!("{}{}", publish.public_url(), versioned_key(&key, &"0".repeat(32)));
+            assert!(url.len() <= 128, "{} is too long for the firmware", url);
+        }
+        // the target, its credentials and refresh policies stay on the server, hashes are only added to published
+        // configs
+        let device_config = serde_json::to_string(&config).unwrap();
+        assert!(!device_config.contains("publish"));
+        assert!(!device_config.contains("refresh"));
+        assert!(!device_config.contains("hash"));
+    }
+}
+
+#[tokio::test]
+async fn test_directory_target() {
+    let path = std::env::temp_dir().join("surfboard_test_publish");
+    let _ = fs::remove_dir_all(&path);
+    let target = DirectoryTarget { path: path.clone() };
+
+    target.put("kitchen/report.qoi", b"first".to_vec()).await.unwrap();
+    target.put("kitchen/report.qoi", b"second".to_vec()).await.unwrap();
+    assert_eq!(fs::read(path.join("kitchen/report.qoi")).unwrap(), b"second");
+    assert_eq!(fs::read_dir(path.join("kitchen")).unwrap().count(), 1);
+    assert_eq!(
+        target.get("kitchen/report.qoi").await.unwrap(),
+        Some(b"second".to_vec())
+    );
+    assert_eq!(target.get("kitchen/missing.qoi").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_connections() {
+    let kitchen =
+        publish_config(r#"{ "type": "directory", "path": "/var/www/kitchen", "public_url": "http://nas.local" }"#);
+    let garage =
+        publish_config(r#"{ "type": "directory", "path": "/var/www/garage", "public_url": "http://nas.local" }"#);
+    let mut connections = Connections::default();
+    let first: *const Publisher = connections.connect("0001.json", &kitchen).await;
+    let again: *const Publisher = connections.connect("0001.json", &kitchen).await;
+    // the same publisher every cycle
+    assert_eq!(first, again);
+
+    // a new one once the device's "publish" changes
+    let Publisher::Directory(target) = connections.connect("0001.json", &garage).await else {
+        panic!("Not a directory target");
+    };
+    assert_eq!(target.path, std::path::Path::new("/var/www/garage"));
+    let Publisher::Directory(target) = connections.connect("0002.json", &kitchen).await else {
+        panic!("Not a directory target");
+    };
+    assert_eq!(target.path, std::path::Path::new("/var/www/kitchen"));
+}
+
+#[tokio::test]
+async fn test_skip_unchanged_upload() {
+    let path = std::env::temp_dir().join("surfboard_test_publish_unchanged");
+    let _ = fs::remove_dir_all(&path);
+    let target = DirectoryTarget { path: path.clone() };
+
+    assert_eq!(content_hash(b"qoif"), "8267c7483f0ecd1256b18bc6f78991ed");
+    assert_eq!(target.stored_hash("report.qoi").await.unwrap(), None);
+    assert!(put_if_changed(&target, "report.qoi", b"qoif".to_vec()).await.unwrap());
+    assert_eq!(
+        target.stored_hash("report.qoi").await.unwrap(),
+        Some(content_hash(b"qoif"))
+    );
+    assert!(!put_if_changed(&target, "report.qoi", b"qoif".to_vec()).await.unwrap());
+    assert!(put_if_changed(&target, "report.qoi", b"qoif2".to_vec()).await.unwrap());
+}
+
+// `make test-minio` starts MinIO with a public `surfboard` bucket and runs this
+#[tokio::test]
+#[ignore]
+async fn test_s3_compatible_target() {
+    let publish =
+        publish_config(r#"{ "type": "s3_compatible", "endpoint": "http://127.0.0.1:9000", "bucket": "surfboard" }"#);
+    let url = format!("{}test/report.qoi", publish.public_url());
+    publish
+        .connect()
+        .await
+        .put(&publish.key(&url).unwrap(), b"qoif".to_vec())
+        .await
+        .unwrap();
+
+    let response = reqwest::get(&url).await.unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"qoif");
+
+    // the ETag MinIO and S3 keep is the hash the scraper compares against
+    let target = publish.connect().await;
+    let key = publish.key(&url).unwrap();
+    assert_eq!(target.stored_hash(&key).await.unwrap(), Some(content_hash(b"qoif")));
+    assert_eq!(target.get(&key).await.unwrap(), Some(b"qoif".to_vec()));
+    assert_eq!(target.get("test/missing.qoi").await.unwrap(), None);
+    assert!(!put_if_changed(&target, &key, b"qoif".to_vec()).await.unwrap());
+}
@@ -0,0 +1,151 @@
+use chrono::{DateTime, FixedOffset, NaiveTime, TimeDelta, TimeZone};
+
+use surfboard_scraper::device_config::ScreenConfiguration;
+use surfboard_scraper::refresh::{RETRY_AFTER, RefreshPolicy, Scheduler, TICK, TimeWindow};
+
+fn screen(value: serde_json::Value) -> ScreenConfiguration {
+    serde_json::from_value(value).unwrap()
+}
+
+fn surf_report() -> ScreenConfiguration {
+    screen(serde_json::json!({
+        "id": "surf-report-24h",
+        "key": "SurfReport24h",
+        "params": { "spot_id": "5842041f4e65fad6a7708807" },
+        "url": "https://example.com/surf-report-24h.qoi",
+        "refresh": { "interval": {
+            "every_minutes": 180,
+            "windows": [{ "from": "05:00", "to": "12:00", "every_minutes": 30 }],
+            "quiet_hours": [{ "from": "22:00", "to": "05:00" }]
+        } }
+    }))
+}
+
+fn message(text: &str) -> ScreenConfiguration {
+    screen(serde_json::json!({
+        "id": "message",
+        "key": "Message",
+        "params": { "text": text },
+        "url": "https://example.com/message.qoi"
+    }))
+}
+
+/// Local time on the Pacific coast in summer
+fn at(hour: u32, minute: u32) -> DateTime<FixedOffset> {
+    FixedOffset::west_opt(7 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2024, 8, 10, hour, minute, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_time_window() {
+    let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+    let night = TimeWindow {
+        from: time(22),
+        to: time(5),
+    };
+    assert!(night.contains(time(23)) && night.contains(time(0)) && night.contains(time(4)));
+    assert!(!night.contains(time(5)) && !night.contains(time(12)));
+}
+
+#[test]
+fn test_interval_refresh() {
+    let screen = surf_report();
+    let mut scheduler = Scheduler::default();
+    assert!(scheduler.is_due(&screen, &at(6, 0)));
+    scheduler.ran(&screen, &at(6, 0), true);
+
+    // every 30 minutes in the morning
+    assert!(!scheduler.is_due(&screen, &at(6, 25)));
+    assert!(scheduler.is_due(&screen, &at(6, 30)));
+    assert_eq!(scheduler.next_run(&screen, &at(6, 0)), Some(at(6, 30).to_utc()));
+
+    // every 3 hours in the afternoon, nothing in quiet hours until 5am
+    scheduler.ran(&screen, &at(14, 0), true);
+    assert!(!scheduler.is_due(&screen, &at(16, 55)));
+    assert!(scheduler.is_due(&screen, &at(17, 0)));
+    scheduler.ran(&screen, &at(20, 0), true);
+    assert!(!scheduler.is_due(&screen, &at(23, 0)));
+    assert_eq!(
+        scheduler.next_run(&screen, &at(20, 0)),
+        Some((at(0, 0) + TimeDelta::days(1) + TimeDelta::hours(5)).to_utc())
+    );
+}
+
+#[test]
+fn test_failed_refresh_is_retried() {
+    let screen = surf_report();
+    let mut scheduler = Scheduler::default();
+    scheduler.ran(&screen, &at(14, 0), false);
+    assert!(!scheduler.is_due(&screen, &(at(14, 0) + RETRY_AFTER - TICK)));
+    assert!(scheduler.is_due(&screen, &(at(14, 0) + RETRY_AFTER)));
+}
+
+#[test]
+fn test_server_side_change_refreshes() {
+    let original = message("Surf's up");
+    let mut scheduler = Scheduler::default();
+    scheduler.ran(&original, &at(6, 0), true);
+    assert!(!scheduler.is_due(&original, &at(7, 0)));
+
+    let refreshed = ScreenConfiguration {
+        refresh: Some(RefreshPolicy::Interval(Default::default())),
+        ..original.clone()
+    };
+    assert!(scheduler.is_due(&refreshed, &at(7, 0)));
+    let stale_sooner = ScreenConfiguration {
+        stale_after_hours: 1,
+        ..original.clone()
+    };
+    assert!(scheduler.is_due(&stale_sooner, &at(7, 0)));
+
+    // the same config parsed again, its params iterate in another order
+    let reloaded = screen(serde_json::json!({
+        "id": "message",
+        "key": "Message",
+        "params": { "text": "Surf's up", "qr_code": "https://example.com", "title": "Today" },
+        "url": "https://example.com/message.qoi"
+    }));
+    scheduler.ran(&reloaded, &at(6, 0), true);
+    for _ in 0..10 {
+        let reparsed: ScreenConfiguration = serde_json::from_str(&serde_json::to_string(&reloaded).unwrap()).unwrap();
+        assert!(!scheduler.is_due(&reparsed, &at(7, 0)));
+    }
+}
+
+#[test]
+fn test_photo_refresh() {
+    let photo = |path: &str| {
+        screen(serde_json::json!({
+            "id": "photo",
+            "key": "Photo",
+            "params": { "path": path, "chromatic": false },
+            "url": "https://example.com/photo.qoi"
+        }))
+    };
+    // a directory rotates through its photos, a single photo only changes with the config
+    assert!(matches!(photo("deploy").refresh_policy(), RefreshPolicy::Interval(_)));
+    assert_eq!(photo("deploy/photo.png").refresh_policy(), RefreshPolicy::OnChange);
+
+    let screen = photo("deploy");
+    let mut scheduler = Scheduler::default();
+    scheduler.ran(&screen, &at(6, 0), true);
+    assert!(scheduler.is_due(&screen, &(at(6, 0) + TimeDelta::hours(3))));
+}
+
+#[test]
+fn test_on_change_refresh() {
+    let screen = message("Surf's up");
+    assert_eq!(screen.refresh_policy(), RefreshPolicy::OnChange);
+    assert!(matches!(surf_report().refresh_policy(), RefreshPolicy::Interval(_)));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.ran(&screen, &at(6, 0), true);
+    assert!(!scheduler.is_due(&screen, &(at(6, 0) + TimeDelta::days(7))));
+    assert_eq!(scheduler.next_run(&screen, &at(6, 0)), None);
+    assert!(scheduler.is_due(&message("Flat all week"), &at(6, 5)));
+
+    // the policy stays on the server
+    assert!(!serde_json::to_string(&surf_report()).unwrap().contains("refresh"));
+}
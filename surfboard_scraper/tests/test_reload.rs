@@ -0,0 +1,40 @@
+use chrono::TimeDelta;
+use std::process::Command;
+
+use surfboard_scraper::reload::{Wakeup, Wakeups, directories_version};
+
+fn send_signal(name: &str) {
+    let status = Command::new("kill")
+        .args([format!("-{}", name), std::process::id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// one test so signals sent to the test process don't reach another test's `Wakeups`
+#[tokio::test]
+async fn test_wakeups() {
+    let directory = std::env::temp_dir().join("surfboard_test_reload");
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(directory.join("0001.json"), "{}").unwrap();
+    let version = directories_version(vec![directory.clone()]);
+
+    let mut wakeups = Wakeups::new(&directory).unwrap();
+    assert_eq!(wakeups.wait(TimeDelta::milliseconds(50)).await.unwrap(), Wakeup::Tick);
+
+    // an edited config is picked up without waiting for the tick
+    std::fs::write(directory.join("0001.json"), r#"{ "screens": [] }"#).unwrap();
+    assert_ne!(directories_version(vec![directory.clone()]), version);
+    assert_eq!(wakeups.wait(TimeDelta::hours(3)).await.unwrap(), Wakeup::Reload);
+
+    send_signal("HUP");
+    assert_eq!(wakeups.wait(TimeDelta::hours(3)).await.unwrap(), Wakeup::Reload);
+
+    // a shutdown during a tick is noticed between devices, then ends the wait
+    assert!(!wakeups.shutdown_requested());
+    send_signal("TERM");
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert!(wakeups.shutdown_requested());
+    assert_eq!(wakeups.wait(TimeDelta::hours(3)).await.unwrap(), Wakeup::Shutdown);
+}
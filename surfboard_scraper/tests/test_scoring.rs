@@ -0,0 +1,119 @@
+use surfboard_scraper::scoring::{HourScore, SessionPreferences, best_window_per_day, best_windows, score_hours};
+use surfboard_scraper::surfline_types::tide::{TideMeasurement, TideType};
+use surfboard_scraper::surfline_types::wave::{WaveMeasurement, WaveMeasurementSurf};
+use surfboard_scraper::surfline_types::wind::{WindDirectionType, WindMeasurement};
+
+// 2024-08-10 00:00 PDT
+const START: i64 = 1723273200;
+const HOUR: i64 = 3600;
+
+fn wave(hour: i64, min: i32, max: i32) -> WaveMeasurement {
+    WaveMeasurement {
+        timestamp: START + hour * HOUR,
+        surf: WaveMeasurementSurf {
+            min,
+            max,
+            plus: false,
+            human_relation: String::new(),
+        },
+    }
+}
+
+fn wind(hour: i64, speed: f32, direction_type: WindDirectionType) -> WindMeasurement {
+    WindMeasurement {
+        timestamp: START + hour * HOUR,
+        utc_offset: -7,
+        direction: 0.,
+        direction_type,
+        speed,
+    }
+}
+
+fn tide(hour: i64, height: f32) -> TideMeasurement {
+    TideMeasurement {
+        height,
+        timestamp: START + hour * HOUR,
+        r#type: TideType::NORMAL,
+        utc_offset: -7,
+    }
+}
+
+fn scores(values: &[f32]) -> Vec<HourScore> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(hour, &score)| HourScore {
+            timestamp: START + hour as i64 * HOUR,
+            utc_offset: -7,
+            score,
+        })
+        .collect()
+}
+
+#[test]
+fn test_score_hours() {
+    let preferences: SessionPreferences =
+        serde_json::from_str(r#"{ "min_wave_height": 3, "ideal_tide": [1.0, 3.0] }"#).expect("Failed to parse");
+    assert_eq!(preferences.max_wind_speed, 12.);
+
+    let waves = [
+        wave(0, 1, 2),
+        wave(1, 3, 4),
+        wave(2, 6, 8),
+        wave(3, 6, 8),
+        wave(4, 6, 8),
+    ];
+    let wind = [
+        wind(0, 2., WindDirectionType::Onshore),
+        wind(1, 2., WindDirectionType::Onshore),
+        wind(2, 2., WindDirectionType::Onshore),
+        wind(3, 15., WindDirectionType::Onshore),
+        wind(4, 15., WindDirectionType::Offshore),
+    ];
+    let tides = [tide(0, 2.), tide(2, 2.), tide(4, 6.)];
+    let scores: Vec<f32> = score_hours(&waves, &wind, &tides, &preferences)
+        .iter()
+        .map(|hour| hour.score)
+        .collect();
+
+    // too small
+    assert_eq!(scores[0], 0.);
+    // big enough, glassy and on the ideal tide
+    assert!(scores[1] > 0.5 && scores[1] < scores[2]);
+    assert_eq!(scores[2], 1.);
+    // blown out onshore
+    assert_eq!(scores[3], 0.);
+    // offshore would be fine, but the tide is too high
+    assert_eq!(scores[4], 0.);
+}
+
+#[test]
+fn test_best_windows() {
+    let windows = best_windows(&scores(&[0., 0.2, 0.9, 1., 0.8, 0.3, 0., 0.6, 0.7, 0.2]), 2);
+    assert_eq!(windows.len(), 2);
+    assert_eq!((windows[0].start, windows[0].end), (START + 2 * HOUR, START + 5 * HOUR));
+    assert_eq!((windows[1].start, windows[1].end), (START + 7 * HOUR, START + 9 * HOUR));
+    assert!(windows[0].score > windows[1].score);
+
+    // windows stop growing at four hours
+    let windows = best_windows(&scores(&[1.; 10]), 1);
+    assert_eq!(windows[0].end - windows[0].start, 4 * HOUR);
+
+    // nothing surfable, no windows
+    assert!(best_windows(&scores(&[0.; 10]), 2).is_empty());
+}
+
+#[test]
+fn test_best_window_per_day() {
+    let mut values = vec![0.; 48];
+    values[9] = 0.8;
+    values[33] = 0.5;
+    values[34] = 0.6;
+    let windows = best_window_per_day(&scores(&values));
+    assert_eq!(windows.len(), 2);
+    assert_eq!(windows[0].start, START + 9 * HOUR);
+    assert_eq!(
+        (windows[1].start, windows[1].end),
+        (START + 33 * HOUR, START + 35 * HOUR)
+    );
+}
@@ -0,0 +1,92 @@
+use clap::Parser;
+use reqwest::{Method, StatusCode, header};
+use std::net::SocketAddr;
+
+use surfboard_scraper::{
+    cli::{Cli, Command},
+    serve::{etag, router},
+};
+
+async fn start_server(directory: std::path::PathBuf) -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router(directory)).await.unwrap() });
+    address
+}
+
+#[tokio::test]
+async fn test_serve_published_files() {
+    let directory = std::env::temp_dir().join("surfboard_test_serve");
+    std::fs::create_dir_all(directory.join("kitchen")).unwrap();
+    std::fs::write(directory.join("0001.json"), br#"{"screens":[]}"#).unwrap();
+    std::fs::write(directory.join("kitchen/report.qoi"), b"qoif").unwrap();
+    let address = start_server(directory).await;
+    let client = reqwest::Client::new();
+    let url = |path: &str| format!("http://{}{}", address, path);
+
+    let response = client.get(url("/0001.json")).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(response.headers()[header::CONTENT_LENGTH], "14");
+    assert_eq!(
+        response.headers()[header::ETAG].to_str().unwrap(),
+        etag(br#"{"screens":[]}"#)
+    );
+
+    // the device already has this version
+    let tag = etag(b"qoif");
+    let response = client
+        .get(url("/kitchen/report.qoi"))
+        .header(header::IF_NONE_MATCH, &tag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert!(response.bytes().await.unwrap().is_empty());
+    let response = client
+        .get(url("/kitchen/report.qoi"))
+        .header(header::IF_NONE_MATCH, "\"stale\"")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"qoif");
+
+    let response = client
+        .request(Method::HEAD, url("/kitchen/report.qoi"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_LENGTH], "4");
+    assert_eq!(response.headers()[header::ETAG].to_str().unwrap(), tag);
+
+    assert_eq!(
+        client.get(url("/missing.qoi")).send().await.unwrap().status(),
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        client
+            .get(url("/kitchen/..%2F0001.json"))
+            .send()
+            .await
+            .unwrap()
+            .status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+fn test_serve_args() {
+    let serve = |args: &[&str]| match Cli::try_parse_from([&["surfboard_scraper", "serve"], args].concat()) {
+        Ok(Cli {
+            command: Some(Command::Serve(args)),
+            ..
+        }) => Ok(args.into_options()),
+        Ok(cli) => panic!("Not a serve: {:?}", cli),
+        Err(e) => Err(e),
+    };
+    let options = serve(&["--port", "9000", "--directory", "/srv/surfboard"]).unwrap();
+    assert_eq!(options.address.port(), 9000);
+    assert_eq!(options.directory, std::path::Path::new("/srv/surfboard"));
+    assert!(serve(&["--directory"]).is_err());
+}
@@ -0,0 +1,161 @@
+use chrono::{TimeZone, Utc};
+use embedded_graphics_simulator::OutputSettingsBuilder;
+use image::{Rgb, RgbImage};
+use std::path::{Path, PathBuf};
+
+use surfboard_scraper::device_config::{Configuration, ScreenConfiguration};
+use surfboard_scraper::http::{DataSource, fixture_path};
+use surfboard_scraper::screen::{RenderContext, ScreenIdentifier};
+
+const FIXTURE_DIRECTORY: &str = "tests/data/fixtures";
+const GOLDEN_DIRECTORY: &str = "tests/data/golden";
+
+// room for float rounding in image scaling to differ between platforms, a moved label is far more than this
+const MAX_DIFF_PIXELS: usize = 200;
+
+/// Set to rewrite the golden images from the current rendering instead of comparing against them
+const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+fn fixture_context() -> RenderContext {
+    RenderContext {
+        data_source: DataSource::Fixtures(FIXTURE_DIRECTORY.into()),
+        // 6am at the spot on the first day of the fixtures
+        frozen_time: Some(Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap()),
+        ..RenderContext::default()
+    }
+}
+
+fn diff_directory() -> PathBuf {
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("snapshots");
+    std::fs::create_dir_all(&directory).expect("Failed to create diff directory");
+    directory
+}
+
+/// Golden image faded out with the differing pixels in red
+fn diff_image(golden: &RgbImage, actual: &RgbImage) -> (RgbImage, usize) {
+    let mut diff_pixels = 0;
+    let diff = RgbImage::from_fn(golden.width(), golden.height(), |x, y| {
+        let (expected, pixel) = (golden.get_pixel(x, y), actual.get_pixel(x, y));
+        if expected != pixel {
+            diff_pixels += 1;
+            Rgb([255, 0, 0])
+        } else {
+            Rgb(expected.0.map(|channel| 192 + channel / 4))
+        }
+    });
+    (diff, diff_pixels)
+}
+
+/// Compare against `<GOLDEN_DIRECTORY>/<name>.png`, a failure leaves the rendering and a diff next to each other
+fn assert_snapshot(name: &str, actual: &RgbImage) {
+    let golden_path = Path::new(GOLDEN_DIRECTORY).join(format!("{}.png", name));
+    if std::env::var_os(UPDATE_SNAPSHOTS).is_some() {
+        actual.save(&golden_path).expect("Failed to update golden image");
+        return;
+    }
+    let actual_path = diff_directory().join(format!("{}.png", name));
+    let golden = match image::open(&golden_path) {
+        Ok(golden) => golden.to_rgb8(),
+        Err(_) => {
+            actual.save(&actual_path).expect("Failed to save rendering");
+            panic!(
+                "No golden image {:?}, rendered to {:?}. Run with {}=1 to add it",
+                golden_path, actual_path, UPDATE_SNAPSHOTS
+            );
+        }
+    };
+    if golden.dimensions() != actual.dimensions() {
+        actual.save(&actual_path).expect("Failed to save rendering");
+        panic!(
+            "{} is {:?}, golden image is {:?}",
+            name,
+            actual.dimensions(),
+            golden.dimensions()
+        );
+    }
+    let (diff, diff_pixels) = diff_image(&golden, actual);
+    if diff_pixels > MAX_DIFF_PIXELS {
+        let diff_path = diff_directory().join(format!("{}-diff.png", name));
+        actual.save(&actual_path).expect("Failed to save rendering");
+        diff.save(&diff_path).expect("Failed to save diff");
+        panic!(
+            "{} differs from {:?} in {} pixels, see {:?}. Run with {}=1 if the change is intended",
+            name, golden_path, diff_pixels, diff_path, UPDATE_SNAPSHOTS
+        );
+    }
+}
+
+// exhaustive so a new screen type doesn't build until it is added to the fixture screens
+fn screen_index(key: &ScreenIdentifier) -> usize {
+    match key {
+        ScreenIdentifier::SurfReport24h => 0,
+        ScreenIdentifier::SurfReportWeek => 1,
+        ScreenIdentifier::Message => 2,
+        ScreenIdentifier::Photo => 3,
+    }
+}
+
+#[tokio::test]
+async fn test_screen_snapshots() {
+    let config: Configuration = serde_json::from_str(
+        &std::fs::read_to_string(Path::new(FIXTURE_DIRECTORY).join("screens.json")).expect("Failed to read screens"),
+    )
+    .expect("Failed to parse screens");
+    let context = fixture_context();
+
+    let mut covered = [false; 4];
+    for screen in &config.screens {
+        covered[screen_index(&screen.key)] = true;
+        let display = screen
+            .draw_to_display(&context)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to draw {}: {:?}", screen.id, e));
+        let output_settings = OutputSettingsBuilder::new().scale(1).build();
+        let output_image = display.to_rgb_output_image(&output_settings);
+        let buffer = output_image.as_image_buffer();
+        let actual = RgbImage::from_raw(buffer.width(), buffer.height(), buffer.into_raw().to_vec()).unwrap();
+        assert_snapshot(&screen.id, &actual);
+    }
+    assert_eq!(covered, [true; 4], "Every screen type needs a snapshot");
+}
+
+#[tokio::test]
+async fn test_screen_without_forecast() {
+    // the partial fixtures above still render, a spot where every forecast fetch fails has nothing to publish
+    let context = fixture_context();
+    for key in ["SurfReport24h", "SurfReportWeek"] {
+        let screen: ScreenConfiguration = serde_json::from_value(serde_json::json!({
+            "id": "missing",
+            "key": key,
+            "params": { "spot_id": "missing" },
+            "url": "https://example.com/missing.qoi"
+        }))
+        .unwrap();
+        let error = screen.draw_to_display(&context).await.expect_err("Drew a screen without data");
+        assert!(format!("{:#}", error).contains("No forecast data for spot missing"));
+    }
+}
+
+#[test]
+fn test_snapshot_diff() {
+    let golden = RgbImage::from_pixel(4, 2, Rgb([255, 255, 255]));
+    let mut actual = golden.clone();
+    actual.put_pixel(1, 1, Rgb([0, 0, 0]));
+
+    let (diff, diff_pixels) = diff_image(&golden, &actual);
+    assert_eq!(diff_pixels, 1);
+    assert_eq!(*diff.get_pixel(1, 1), Rgb([255, 0, 0]));
+    assert_eq!(*diff.get_pixel(0, 0), Rgb([255, 255, 255]));
+}
+
+#[test]
+fn test_fixture_path() {
+    let path = fixture_path(
+        "https://services.surfline.com/kbyg/spots/forecasts/wave?spotId=5842041f4e65fad6a7708807&days=7&intervalHours=3",
+    )
+    .unwrap();
+    assert_eq!(path, Path::new("5842041f4e65fad6a7708807/wave_7d.json"));
+    let path = fixture_path("https://services.surfline.com/kbyg/spots/details?spotId=5842041f4e65fad6a7708807").unwrap();
+    assert_eq!(path, Path::new("5842041f4e65fad6a7708807/details.json"));
+    assert!(fixture_path("https://services.surfline.com/kbyg/spots/details").is_err());
+}
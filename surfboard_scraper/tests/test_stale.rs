@@ -0,0 +1,187 @@
+use chrono::{TimeDelta, TimeZone, Utc};
+use image::{Rgb, RgbImage};
+
+use surfboard_scraper::device_config::ScreenConfiguration;
+use surfboard_scraper::http::DataSource;
+use surfboard_scraper::manifest::ScreenManifest;
+use surfboard_scraper::metrics::METRICS;
+use surfboard_scraper::publish::{DirectoryTarget, PublishConfig, PublishTarget};
+use surfboard_scraper::screen::RenderContext;
+use surfboard_scraper::stale::StaleScreens;
+
+const SPOT_ID: &str = "5842041f4e65fad6a7708807";
+const BANNER_COLOR: Rgb<u8> = Rgb([255, 0, 0]);
+
+fn surf_report(spot_id: &str) -> ScreenConfiguration {
+    serde_json::from_value(serde_json::json!({
+        "id": "surf-report-24h",
+        "key": "SurfReport24h",
+        "params": { "spot_id": spot_id },
+        "url": "https://example.com/surf-report-24h.qoi"
+    }))
+    .unwrap()
+}
+
+fn context_after(hours: i64) -> RenderContext {
+    RenderContext {
+        data_source: DataSource::Fixtures("tests/data/fixtures".into()),
+        frozen_time: Some(Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap() + TimeDelta::hours(hours)),
+        ..RenderContext::default()
+    }
+}
+
+fn decode(qoi: &[u8]) -> RgbImage {
+    image::load_from_memory_with_format(qoi, image::ImageFormat::Qoi)
+        .unwrap()
+        .to_rgb8()
+}
+
+/// The rendering and whether it's the stale fallback
+async fn render(stale_screens: &mut StaleScreens, spot_id: &str, hours: i64) -> anyhow::Result<(RgbImage, bool)> {
+    let rendered = stale_screens
+        .render(&surf_report(spot_id), &context_after(hours))
+        .await?;
+    Ok((decode(&rendered.bytes), rendered.stale))
+}
+
+#[tokio::test]
+async fn test_stale_screen() {
+    let mut stale_screens = StaleScreens::default();
+    let (fresh, is_stale) = render(&mut stale_screens, SPOT_ID, 0).await.unwrap();
+    assert!(!is_stale);
+
+    // the spot's data disappears, the last upload stays up until the screen is stale
+    assert!(render(&mut stale_screens, "missing", 3).await.is_err());
+    let (stale, is_stale) = render(&mut stale_screens, "missing", 6).await.unwrap();
+    assert!(is_stale);
+    assert_eq!(
+        stale_screens.fresh_since(&surf_report(SPOT_ID).url),
+        context_after(0).frozen_time
+    );
+
+    assert_eq!(*stale.get_pixel(0, 0), BANNER_COLOR);
+    assert_ne!(*fresh.get_pixel(0, 0), BANNER_COLOR);
+    for (x, y, pixel) in stale.enumerate_pixels().filter(|(_, y, _)| *y >= 48) {
+        assert_eq!(pixel, fresh.get_pixel(x, y), "Last good data changed at {},{}", x, y);
+    }
+
+    // a good rendering resets the clock
+    assert!(render(&mut stale_screens, SPOT_ID, 9).await.is_ok());
+    assert!(render(&mut stale_screens, "missing", 12).await.is_err());
+}
+
+#[tokio::test]
+async fn test_stale_screen_without_history() {
+    let mut stale_screens = StaleScreens::default();
+    assert!(render(&mut stale_screens, "missing", 0).await.is_err());
+    let (stale, is_stale) = render(&mut stale_screens, "missing", 6).await.unwrap();
+    assert!(is_stale);
+    assert_eq!(*stale.get_pixel(0, 0), BANNER_COLOR);
+    assert_eq!(*stale.get_pixel(400, 240), Rgb([255, 255, 255]));
+}
+
+#[tokio::test]
+async fn test_stale_screen_too_big() {
+    // noise that draws fine but doesn't fit the device's buffer as QOI
+    let path = std::env::temp_dir().join("surfboard_test_stale_noise.png");
+    RgbImage::from_fn(600, 200, |x, y| match (x * 7919 + y * 104729) % 13 < 6 {
+        true => Rgb([0, 0, 0]),
+        false => Rgb([255, 255, 255]),
+    })
+    .save(&path)
+    .unwrap();
+    let screen: ScreenConfiguration = serde_json::from_value(serde_json::json!({
+        "id": "message",
+        "key": "Message",
+        "params": { "message": "Too big", "image": path },
+        "url": "https://example.com/too-big.qoi"
+    }))
+    .unwrap();
+
+    let mut stale_screens = StaleScreens::default();
+    let error = stale_screens
+        .render(&screen, &context_after(0))
+        .await
+        .expect_err("Fits the buffer");
+    assert!(
+        format!("{:#}", error).contains("the device buffer holds"),
+        "{:#}",
+        error
+    );
+    // counted as a failure and republished as stale like one that fails to draw
+    let stale = stale_screens.render(&screen, &context_after(6)).await.unwrap();
+    assert!(stale.stale);
+    assert_eq!(*decode(&stale.bytes).get_pixel(0, 0), BANNER_COLOR);
+    assert_eq!(METRICS.render_failures.with_label_values(&[&screen.url]).get(), 2);
+}
+
+#[tokio::test]
+async fn test_stale_screen_restored() {
+    let publish: PublishConfig = serde_json::from_value(
+        serde_json::json!({ "type": "directory", "path": "", "public_url": "https://example.com" }),
+    )
+    .unwrap();
+    let path = std::env::temp_dir().join("surfboard_test_stale_restored");
+    let _ = std::fs::remove_dir_all(&path);
+    let target = DirectoryTarget { path };
+    let screen = surf_report(SPOT_ID);
+
+    // an earlier run published the screen, this one never rendered it
+    let fresh = StaleScreens::default()
+        .render(&screen, &context_after(0))
+        .await
+        .unwrap()
+        .bytes;
+    target
+        .put("surf-report-24h.0123456789abcdef.qoi", fresh.clone())
+        .await
+        .unwrap();
+    let rendered_at = context_after(0).frozen_time.unwrap();
+    let entry = ScreenManifest {
+        url: "https://example.com/surf-report-24h.0123456789abcdef.qoi".to_string(),
+        ..ScreenManifest::new(&screen, "0123456789abcdef".into(), rendered_at, rendered_at, None)
+    };
+    let mut stale_screens = StaleScreens::default();
+    stale_screens.restore(&screen, &entry, &publish, &target).await.unwrap();
+    assert_eq!(stale_screens.fresh_since(&screen.url), Some(rendered_at));
+
+    // stale counts from the published image, and falls back to it rather than a blank screen
+    assert!(render(&mut stale_screens, "missing", 3).await.is_err());
+    let (stale, is_stale) = render(&mut stale_screens, "missing", 6).await.unwrap();
+    assert!(is_stale);
+    let fresh = decode(&fresh);
+    assert_eq!(*stale.get_pixel(0, 0), BANNER_COLOR);
+    for (x, y, pixel) in stale.enumerate_pixels().filter(|(_, y, _)| *y >= 48) {
+        assert_eq!(pixel, fresh.get_pixel(x, y), "Published image changed at {},{}", x, y);
+    }
+
+    // history of its own wins over what's published
+    let missing = ScreenManifest {
+        url: "https://example.com/surf-report-24h.fedcba9876543210.qoi".to_string(),
+        ..entry
+    };
+    stale_screens
+        .restore(&screen, &missing, &publish, &target)
+        .await
+        .unwrap();
+    assert_eq!(stale_screens.fresh_since(&screen.url), Some(rendered_at));
+    let mut stale_screens = StaleScreens::default();
+    stale_screens
+        .restore(&screen, &missing, &publish, &target)
+        .await
+        .unwrap();
+    assert_eq!(stale_screens.fresh_since(&screen.url), None);
+}
+
+#[test]
+fn test_stale_after_config() {
+    let screen = surf_report(SPOT_ID);
+    assert_eq!(screen.stale_after_hours, 6);
+    // a server-side setting, devices never see it
+    assert!(!serde_json::to_string(&screen).unwrap().contains("stale_after_hours"));
+
+    let mut value = serde_json::to_value(&screen).unwrap();
+    value["stale_after_hours"] = 24.into();
+    let screen: ScreenConfiguration = serde_json::from_value(value).unwrap();
+    assert_eq!(screen.stale_after_hours, 24);
+}
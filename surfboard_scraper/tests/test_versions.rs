@@ -0,0 +1,146 @@
+use chrono::{NaiveDate, TimeDelta, TimeZone, Utc};
+use std::collections::HashSet;
+
+use surfboard_scraper::device_config::Configuration;
+use surfboard_scraper::publish::{DirectoryTarget, PublishConfig, PublishTarget};
+use surfboard_scraper::versions::{RetiredVersions, VERSION_GRACE_PERIOD, versioned_key};
+
+const HASH: &str = "8267c7483f0ecd1256b18bc6f78991ed";
+
+#[test]
+fn test_versioned_key() {
+    assert_eq!(versioned_key("report.qoi", HASH), "report.8267c7483f0ecd12.qoi");
+    assert_eq!(
+        versioned_key("kitchen/report.qoi", HASH),
+        "kitchen/report.8267c7483f0ecd12.qoi"
+    );
+    assert_eq!(versioned_key("v1.0/report", HASH), "v1.0/report.8267c7483f0ecd12");
+}
+
+#[tokio::test]
+async fn test_retired_versions() {
+    let path = std::env::temp_dir().join("surfboard_test_versions");
+    let _ = std::fs::remove_dir_all(&path);
+    let publish: PublishConfig = serde_json::from_value(serde_json::json!({
+        "type": "directory", "path": path, "public_url": "http://nas.local/"
+    }))
+    .unwrap();
+    let target = DirectoryTarget { path: path.clone() };
+    let screen_url = "http://nas.local/kitchen/report.qoi";
+
+    let old = versioned_key("kitchen/report.qoi", "0000000000000000");
+    let live = versioned_key("kitchen/report.qoi", HASH);
+    for key in [
+        old.as_str(),
+        live.as_str(),
+        "kitchen/report.qoi",
+        "kitchen/report.partial.qoi",
+    ] {
+        target.put(key, b"qoif".to_vec()).await.unwrap();
+    }
+    let live_urls = HashSet::from([format!("http://nas.local/{}", live)]);
+
+    // the old version gets a grace period from the first time it's seen unused, across `--once` runs that each
+    // start from the state file
+    let now = Utc.with_ymd_and_hms(2024, 8, 10, 13, 0, 0).unwrap();
+    let state_file = path.join("scraper_state.json");
+    assert_eq!(RetiredVersions::load(&state_file).unwrap(), RetiredVersions::default());
+    for hours in [0, 3, 21] {
+        let mut retired = RetiredVersions::load(&state_file).unwrap();
+        let deleted = retired
+            .collect(&publish, &target, screen_url, &live_urls, now + TimeDelta::hours(hours))
+            .await
+            .unwrap();
+        assert!(deleted.is_empty());
+        retired.save(&state_file).unwrap();
+    }
+    let mut retired = RetiredVersions::load(&state_file).unwrap();
+    let deleted = retired
+        .collect(&publish, &target, screen_url, &live_urls, now + VERSION_GRACE_PERIOD)
+        .await
+        .unwrap();
+    assert_eq!(deleted, vec![format!("http://nas.local/{}", old)]);
+
+    let mut remaining = target.list("kitchen/report.").await.unwrap();
+    remaining.sort();
+    assert_eq!(
+        remaining,
+        vec![
+            live,
+            "kitchen/report.partial.qoi".to_string(),
+            "kitchen/report.qoi".to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_retired_versions_of_inactive_screens() {
+    let path = std::env::temp_dir().join("surfboard_test_versions_inactive");
+    let _ = std::fs::remove_dir_all(&path);
+    let config: Configuration = serde_json::from_value(serde_json::json!({
+        "screens": [
+            {
+                "id": "report", "key": "Message", "params": { "message": "report" },
+                "url": "http://nas.local/report.qoi"
+            }
+        ],
+        "screen_saver": null,
+        "scheduled_screen_savers": [{
+            "id": "bday", "key": "Message", "params": { "message": "bday" }, "url": "http://nas.local/bday.qoi",
+            "recurring": { "yearly": { "month": 6, "day": 14 } }
+        }],
+        "publish": { "type": "directory", "path": path, "public_url": "http://nas.local/" }
+    }))
+    .unwrap();
+    let publish = config.publish.clone().unwrap();
+    let target = DirectoryTarget { path: path.clone() };
+    let bday = versioned_key("bday.qoi", HASH);
+    let removed = versioned_key("removed.qoi", HASH);
+    for key in [bday.as_str(), removed.as_str()] {
+        target.put(key, b"qoif".to_vec()).await.unwrap();
+    }
+
+    // the day after the birthday only the report is active, nothing points at the birthday version
+    let active = config.active_on(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+    assert!(active.all_screens().all(|screen| screen.id != "bday"));
+    let mut retired = RetiredVersions::default();
+    // a screen the config had in an earlier run
+    retired.screens_of("0001.json", ["http://nas.local/removed.qoi"].into_iter());
+    let screen_urls = retired.screens_of("0001.json", config.all_screens().map(|screen| screen.url.as_str()));
+    assert_eq!(
+        screen_urls,
+        vec![
+            "http://nas.local/bday.qoi",
+            "http://nas.local/removed.qoi",
+            "http://nas.local/report.qoi"
+        ]
+    );
+
+    let now = Utc.with_ymd_and_hms(2025, 6, 15, 13, 0, 0).unwrap();
+    let mut deleted = Vec::new();
+    for hours in [TimeDelta::zero(), VERSION_GRACE_PERIOD] {
+        for screen_url in &screen_urls {
+            deleted.extend(
+                retired
+                    .collect(&publish, &target, screen_url, &HashSet::new(), now + hours)
+                    .await
+                    .unwrap(),
+            );
+        }
+    }
+    assert_eq!(
+        deleted,
+        vec![
+            format!("http://nas.local/{}", bday),
+            format!("http://nas.local/{}", removed)
+        ]
+    );
+    assert!(target.list("").await.unwrap().is_empty());
+
+    // with its versions gone the removed screen is forgotten, configured ones are kept
+    let screen_urls = retired.screens_of("0001.json", config.all_screens().map(|screen| screen.url.as_str()));
+    assert_eq!(
+        screen_urls,
+        vec!["http://nas.local/bday.qoi", "http://nas.local/report.qoi"]
+    );
+}
@@ -0,0 +1,64 @@
+use surfboard_scraper::common::draw_utils::weather_icon;
+use surfboard_scraper::surfline_types::weather::{WeatherCondition, WeatherKind, WeatherMeasurement};
+
+const SURFLINE_CONDITIONS: [&str; 12] = [
+    "CLEAR",
+    "NIGHT_CLEAR",
+    "NIGHT_MOSTLY_CLOUDY",
+    "BRIEF_SHOWERS_POSSIBLE",
+    "NIGHT_BRIEF_SHOWERS",
+    "HEAVY_RAIN",
+    "THUNDER_STORMS",
+    "NIGHT_THUNDER_SHOWERS",
+    "LIGHT_SNOW_SHOWERS",
+    "HAZE",
+    "NIGHT_FOG",
+    "WINDY",
+];
+
+#[test]
+fn test_weather_condition_parsing() {
+    assert_eq!(
+        WeatherCondition::parse("MOSTLY_CLOUDY"),
+        WeatherCondition::Day(WeatherKind::MostlyCloudy)
+    );
+    assert_eq!(
+        WeatherCondition::parse("NIGHT_DRIZZLE"),
+        WeatherCondition::Night(WeatherKind::Drizzle)
+    );
+    assert_eq!(
+        WeatherCondition::parse("VOLCANIC_ASH"),
+        WeatherCondition::Unknown("VOLCANIC_ASH".into())
+    );
+
+    // serialized back to Surfline's strings so cached screen data parses again
+    for condition in SURFLINE_CONDITIONS.iter().chain(&["VOLCANIC_ASH"]) {
+        let measurement: WeatherMeasurement = serde_json::from_value(serde_json::json!({
+            "timestamp": 1723273200,
+            "utcOffset": -7,
+            "condition": condition,
+            "temperature": 60.0
+        }))
+        .expect("Failed to parse measurement");
+        let json = serde_json::to_value(&measurement).expect("Failed to serialize measurement");
+        assert_eq!(json["condition"], *condition);
+    }
+}
+
+#[test]
+fn test_weather_icons() {
+    for condition in SURFLINE_CONDITIONS {
+        assert!(
+            weather_icon(&WeatherCondition::parse(condition)).is_some(),
+            "{}",
+            condition
+        );
+    }
+    assert!(weather_icon(&WeatherCondition::parse("VOLCANIC_ASH")).is_none());
+
+    // clear nights show the moon, cloudy ones look the same day and night
+    let icon = |condition: &str| weather_icon(&WeatherCondition::parse(condition)).unwrap();
+    assert_ne!(icon("CLEAR"), icon("NIGHT_CLEAR"));
+    assert_ne!(icon("MOSTLY_CLEAR"), icon("NIGHT_MOSTLY_CLEAR"));
+    assert_eq!(icon("CLOUDY"), icon("NIGHT_CLOUDY"));
+}
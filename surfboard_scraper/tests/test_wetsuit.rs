@@ -0,0 +1,34 @@
+use surfboard_scraper::surfline_types::report::ReportResult;
+use surfboard_scraper::wetsuit::WetsuitTable;
+
+#[test]
+fn test_wetsuit_recommendation() {
+    let table = WetsuitTable::default();
+    assert_eq!(table.recommend(75.), Some("Boardshorts"));
+    assert_eq!(table.recommend(57.), Some("4/3mm"));
+    assert_eq!(table.recommend(55.), Some("4/3mm"));
+    assert_eq!(table.recommend(40.), Some("6/5mm+hood"));
+
+    let custom: WetsuitTable = serde_json::from_str(
+        r#"[{ "min_temp": 60, "suit": "3/2mm" }, { "min_temp": 50, "suit": "4/3mm" }]"#,
+    )
+    .expect("Failed to parse wetsuit table");
+    assert_eq!(custom.recommend(62.), Some("3/2mm"));
+    assert_eq!(custom.recommend(49.), None);
+}
+
+#[test]
+fn test_report_water_temperature() {
+    let report: ReportResult = serde_json::from_str(
+        r#"{
+            "associated": { "units": { "temperature": "C", "tideHeight": "M" } },
+            "spot": { "name": "Pleasure Point" },
+            "forecast": { "waterTemp": { "min": 12, "max": 14 }, "tide": {} }
+        }"#,
+    )
+    .expect("Failed to parse report");
+    let water_temperature = report.water_temperature();
+    assert_eq!(water_temperature.unit, "C");
+    assert_eq!(water_temperature.average_fahrenheit(), 55.4);
+    assert_eq!(WetsuitTable::default().recommend(water_temperature.average_fahrenheit()), Some("4/3mm"));
+}